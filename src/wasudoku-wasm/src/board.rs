@@ -16,119 +16,290 @@
 * along with WASudoku.  If not, see <https://www.gnu.org/licenses/>.
 */
 
+use crate::constraints::Constraint;
 use std::fmt;
+use std::rc::Rc;
 
-/// A 9x9 Sudoku board.
+/// The smallest supported box order (a 4x4 board).
+pub const MIN_ORDER: u8 = 2;
+/// The largest supported box order (a 25x25 board).
+pub const MAX_ORDER: u8 = 5;
+
+/// A variable-order Sudoku board (box side `order`, side length `order * order`).
+///
+/// Stores the board as a flat `Vec<u8>` of `side * side` cells, where `0`
+/// represents an empty cell and `1..=side` represent filled cells. The
+/// classic 9x9 puzzle is the `order = 3` case.
 ///
-/// Stores the board as a flat array of 81 `u8` cells, where `0` represents
-/// an empty cell and `1` through `9` represent filled cells.
-#[derive(Clone, Copy, PartialEq, Eq)]
+/// Alongside `cells`, the board keeps a `side`-long bitmask per row, column
+/// and box (bit `d-1` set means digit `d` is present in that unit), so
+/// `is_valid_move` and `candidates` are O(1) instead of rescanning cells.
+/// These masks are only ever touched through [`Board::set`], so **mutate
+/// cells through `set`, not by indexing into `cells` directly**, or the
+/// masks will drift out of sync with the board they describe.
+///
+/// Row, column and box rules are always enforced via those bitmasks.
+/// `constraints` carries any extra rule variants layered on top (diagonal,
+/// Windoku, killer cages, ...); see [`crate::constraints`].
+#[derive(Clone)]
 pub struct Board {
-    pub cells: [u8; 81],
+    /// The box side length (e.g. `3` for a classic 9x9 board).
+    pub order: u8,
+    /// `side * side` cells in row-major order.
+    pub cells: Vec<u8>,
+    /// Extra rule variants beyond row/column/box, if any.
+    pub constraints: Vec<Rc<dyn Constraint>>,
+    row_used: Vec<u32>,
+    col_used: Vec<u32>,
+    box_used: Vec<u32>,
 }
 
 impl Board {
-    /// Parse and validate an 81-character string into a `Board`.
+    /// The side length of the board (`order * order`).
+    pub fn side(&self) -> usize {
+        self.order as usize * self.order as usize
+    }
+
+    /// Create an empty board of the given `order`.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if `order` is outside `MIN_ORDER..=MAX_ORDER`.
+    pub fn empty(order: u8) -> Self {
+        assert!(
+            (MIN_ORDER..=MAX_ORDER).contains(&order),
+            "Unsupported board order: {}",
+            order
+        );
+        let side = order as usize * order as usize;
+        Board {
+            order,
+            cells: vec![0; side * side],
+            constraints: Vec::new(),
+            row_used: vec![0; side],
+            col_used: vec![0; side],
+            box_used: vec![0; side],
+        }
+    }
+
+    /// Place (or clear, with `value = 0`) a digit at `index`, keeping the
+    /// row/column/box bitmasks in sync. This is the only way `cells` should
+    /// be mutated after construction.
+    pub fn set(&mut self, index: usize, value: u8) {
+        let side = self.side();
+        let row = index / side;
+        let col = index % side;
+        let box_index = self.box_index(row, col);
+
+        let previous = self.cells[index];
+        if previous != 0 {
+            let mask = 1u32 << (previous - 1);
+            self.row_used[row] &= !mask;
+            self.col_used[col] &= !mask;
+            self.box_used[box_index] &= !mask;
+        }
+
+        if value != 0 {
+            let mask = 1u32 << (value - 1);
+            self.row_used[row] |= mask;
+            self.col_used[col] |= mask;
+            self.box_used[box_index] |= mask;
+        }
+
+        self.cells[index] = value;
+    }
+
+    /// The digits not yet present in `(row, col)`'s row, column or box, as a
+    /// bitmask (bit `d-1` set means `d` is still a valid candidate).
+    pub fn candidates(&self, row: usize, col: usize) -> u32 {
+        let all = (1u32 << self.side()) - 1;
+        let box_index = self.box_index(row, col);
+        all & !(self.row_used[row] | self.col_used[col] | self.box_used[box_index])
+    }
+
+    /// The index of the box containing `(row, col)`.
+    pub fn box_index(&self, row: usize, col: usize) -> usize {
+        let order = self.order as usize;
+        (row / order) * order + (col / order)
+    }
+
+    /// Infer a board `order` from an encoded string length (`order^4` cells).
+    pub(crate) fn order_for_len(len: usize) -> Result<u8, String> {
+        for order in MIN_ORDER..=MAX_ORDER {
+            let side = order as usize * order as usize;
+            if side * side == len {
+                return Ok(order);
+            }
+        }
+        Err(format!(
+            "Invalid board string length: {} does not match any supported order (16, 81, 256, 625)",
+            len
+        ))
+    }
+
+    /// Decode a single board-string character into a cell value.
+    ///
+    /// `.`/`0` is empty, `1`-`9` are 1-9, and `A`-`Z` extend the range up to
+    /// 35 so that orders above 3 (where values exceed 9) can be represented.
+    fn decode_char(ch: char) -> Option<u8> {
+        match ch {
+            '.' | '0' => Some(0),
+            '1'..='9' => Some(ch as u8 - b'0'),
+            'A'..='Z' => Some(ch as u8 - b'A' + 10),
+            _ => None,
+        }
+    }
+
+    /// Encode a cell value back into its board-string character.
+    fn encode_value(value: u8) -> char {
+        if value == 0 {
+            '.'
+        } else if value <= 9 {
+            (b'0' + value) as char
+        } else {
+            (b'A' + value - 10) as char
+        }
+    }
+
+    /// Parse and validate an encoded string into a `Board`.
     ///
-    /// The string is parsed row by row, with `.` or `0` representing empty
-    /// cells. The board is validated in a single pass to ensure no initial
-    /// rule conflicts exist.
+    /// The board order is inferred from the string length (`16`, `81`,
+    /// `256`, or `625` characters), the string is parsed row by row, and the
+    /// board is validated in a single pass to ensure no initial rule
+    /// conflicts exist.
     ///
     /// ### Errors
     ///
-    /// Returns an `Err` if the string is not 81 characters, contains invalid
-    /// characters, or describes a board with initial conflicts.
+    /// Returns an `Err` if the string length does not match a supported
+    /// order, contains invalid characters, or describes a board with
+    /// initial conflicts.
     pub fn from_str(s: &str) -> Result<Self, String> {
-        if s.len() != 81 {
+        let order = Self::order_for_len(s.chars().count())?;
+        let side = order as usize * order as usize;
+
+        let mut cells = vec![0u8; side * side];
+        for (i, char) in s.chars().enumerate() {
+            let digit = Self::decode_char(char).ok_or_else(|| {
+                format!("Invalid character '{}' in board string at index {}", char, i)
+            })?;
+
+            if digit as usize > side {
+                return Err(format!(
+                    "Invalid character '{}' in board string at index {}",
+                    char, i
+                ));
+            }
+            cells[i] = digit;
+        }
+
+        Self::from_cells(order, cells)
+    }
+
+    /// Build a `Board` from already-decoded `cells`, running the same
+    /// single-pass conflict validation used by [`Board::from_str`].
+    ///
+    /// ### Errors
+    ///
+    /// Returns an `Err` if `cells` does not contain exactly `order * order`
+    /// squared entries, or describes a board with initial conflicts.
+    pub(crate) fn from_cells(order: u8, cells: Vec<u8>) -> Result<Self, String> {
+        let side = order as usize * order as usize;
+        if cells.len() != side * side {
             return Err(format!(
-                "Invalid board string length: expected 81, got {}",
-                s.len()
+                "Invalid cell count: expected {} cells for order {}, got {}",
+                side * side,
+                order,
+                cells.len()
             ));
         }
+        let mut board = Board {
+            order,
+            cells,
+            constraints: Vec::new(),
+            row_used: vec![0; side],
+            col_used: vec![0; side],
+            box_used: vec![0; side],
+        };
 
-        let mut cells = [0; 81];
-        let mut rows = [0u16; 9];
-        let mut cols = [0u16; 9];
-        let mut boxes = [0u16; 9];
+        for i in 0..board.cells.len() {
+            let digit = board.cells[i];
+            if digit == 0 {
+                continue;
+            }
 
-        for (i, char) in s.chars().enumerate() {
-            let digit = match char {
-                '.' | '0' => 0,
-                '1'..='9' => char.to_digit(10).unwrap() as u8,
-                _ => {
-                    return Err(format!(
-                        "Invalid character '{}' in board string at index {}",
-                        char, i
-                    ));
-                }
-            };
-            cells[i] = digit;
+            let row = i / side;
+            let col = i % side;
+            let box_index = board.box_index(row, col);
+            let mask = 1u32 << (digit - 1);
 
-            if digit != 0 {
-                let row = i / 9;
-                let col = i % 9;
-                let box_index = (row / 3) * 3 + (col / 3);
-                let mask = 1 << (digit - 1);
-
-                if (rows[row] & mask) != 0
-                    || (cols[col] & mask) != 0
-                    || (boxes[box_index] & mask) != 0
-                {
-                    return Err(String::from(
-                        "Invalid puzzle: initial configuration has conflicts.",
-                    ));
-                }
-                rows[row] |= mask;
-                cols[col] |= mask;
-                boxes[box_index] |= mask;
+            if (board.row_used[row] & mask) != 0
+                || (board.col_used[col] & mask) != 0
+                || (board.box_used[box_index] & mask) != 0
+            {
+                return Err(String::from(
+                    "Invalid puzzle: initial configuration has conflicts.",
+                ));
             }
+            board.row_used[row] |= mask;
+            board.col_used[col] |= mask;
+            board.box_used[box_index] |= mask;
         }
 
-        Ok(Board { cells })
+        Ok(board)
+    }
+
+    /// Build a `Board` from `cells` already known to be conflict-free (e.g. a
+    /// completed solution), skipping the validation pass in [`Board::from_cells`].
+    pub(crate) fn from_cells_unchecked(order: u8, cells: Vec<u8>) -> Self {
+        let side = order as usize * order as usize;
+        let mut board = Board {
+            order,
+            cells: vec![0; cells.len()],
+            constraints: Vec::new(),
+            row_used: vec![0; side],
+            col_used: vec![0; side],
+            box_used: vec![0; side],
+        };
+        for (index, &value) in cells.iter().enumerate() {
+            board.set(index, value);
+        }
+        board
     }
 
     /// Check if placing a number in a cell is valid according to Sudoku rules.
     ///
     /// A move is valid if the number does not already exist in the cell's
-    /// row, column, or 3x3 box.
+    /// row, column, or box, and it satisfies every extra rule in
+    /// `constraints` (diagonal, Windoku, killer cages, ...), if any.
     pub fn is_valid_move(&self, row: usize, col: usize, num: u8) -> bool {
-        for x in 0..9 {
-            if self.cells[row * 9 + x] == num {
-                return false;
-            }
+        let box_index = self.box_index(row, col);
+        let used = self.row_used[row] | self.col_used[col] | self.box_used[box_index];
+        if (used >> (num - 1)) & 1 != 0 {
+            return false;
         }
 
-        for x in 0..9 {
-            if self.cells[x * 9 + col] == num {
-                return false;
-            }
-        }
-
-        let start_row = row - row % 3;
-        let start_col = col - col % 3;
-        for i in 0..3 {
-            for j in 0..3 {
-                if self.cells[(start_row + i) * 9 + (start_col + j)] == num {
-                    return false;
-                }
-            }
-        }
-
-        true
+        let cell = row * self.side() + col;
+        self.constraints
+            .iter()
+            .all(|constraint| constraint.is_satisfied(self, cell, num))
     }
 }
 
-/// Format the board as an 81-character string, using `.` for empty cells.
+/// Format the board as an encoded string, using `.` for empty cells.
 impl fmt::Display for Board {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut s = String::with_capacity(81);
+        let mut s = String::with_capacity(self.cells.len());
         for &cell in self.cells.iter() {
-            if cell == 0 {
-                s.push('.');
-            } else {
-                s.push(std::char::from_digit(cell as u32, 10).unwrap());
-            }
+            s.push(Board::encode_value(cell));
         }
         write!(f, "{}", s)
     }
 }
+
+impl std::str::FromStr for Board {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Board::from_str(s)
+    }
+}
@@ -0,0 +1,160 @@
+/*
+* Copyright (C) 2025  Henrique Almeida
+* This file is part of WASudoku.
+
+* WASudoku is free software: you can redistribute it and/or modify
+* it under the terms of the GNU Affero General Public License as published
+* by the Free Software Foundation, either version 3 of the License, or
+* (at your option) any later version.
+
+* WASudoku is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+* GNU Affero General Public License for more details.
+
+* You should have received a copy of the GNU Affero General Public License
+* along with WASudoku.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Pluggable rule variants layered on top of the classic row/column/box
+//! constraints, which `Board` always enforces through its bitmasks.
+//!
+//! A [`Constraint`] is consulted by [`Board::is_valid_move`](crate::board::Board::is_valid_move)
+//! in addition to the row/column/box check, so the backtracking solver,
+//! `count_solutions`, and the generator all respect it automatically.
+//! `logical_solver`'s human-technique tracer is unaware of constraints
+//! beyond row/column/box: its deductions stay valid on a variant board, but
+//! it never uses the extra rules to make any.
+
+use crate::board::Board;
+use std::rc::Rc;
+
+/// An extra rule governing a fixed set of cells, beyond the row/column/box
+/// constraints `Board` always enforces.
+pub trait Constraint {
+    /// Whether placing `value` at `cell` is still consistent with this
+    /// constraint, given the board's current (pre-placement) state.
+    fn is_satisfied(&self, board: &Board, cell: usize, value: u8) -> bool;
+
+    /// The cells this constraint links together.
+    fn cells(&self) -> &[usize];
+}
+
+/// A set of cells that must not contain the same digit twice, e.g. a
+/// diagonal or a Windoku extra region.
+pub struct UniqueGroup {
+    cells: Vec<usize>,
+}
+
+impl UniqueGroup {
+    pub fn new(cells: Vec<usize>) -> Self {
+        Self { cells }
+    }
+}
+
+impl Constraint for UniqueGroup {
+    fn is_satisfied(&self, board: &Board, cell: usize, value: u8) -> bool {
+        if !self.cells.contains(&cell) {
+            return true;
+        }
+        self.cells
+            .iter()
+            .all(|&other| other == cell || board.cells[other] != value)
+    }
+
+    fn cells(&self) -> &[usize] {
+        &self.cells
+    }
+}
+
+/// A killer-cage constraint: no repeated digit within the cage, and the
+/// cage's digits must sum to exactly `target_sum` once fully filled.
+pub struct KillerCage {
+    cells: Vec<usize>,
+    target_sum: u32,
+}
+
+impl KillerCage {
+    pub fn new(cells: Vec<usize>, target_sum: u32) -> Self {
+        Self { cells, target_sum }
+    }
+}
+
+impl Constraint for KillerCage {
+    fn is_satisfied(&self, board: &Board, cell: usize, value: u8) -> bool {
+        if !self.cells.contains(&cell) {
+            return true;
+        }
+
+        let mut sum = value as u32;
+        let mut filled = 1;
+        for &other in &self.cells {
+            if other == cell {
+                continue;
+            }
+            let existing = board.cells[other];
+            if existing == value {
+                return false;
+            }
+            if existing != 0 {
+                sum += existing as u32;
+                filled += 1;
+            }
+        }
+
+        if sum > self.target_sum {
+            return false;
+        }
+        if filled == self.cells.len() && sum != self.target_sum {
+            return false;
+        }
+        true
+    }
+
+    fn cells(&self) -> &[usize] {
+        &self.cells
+    }
+}
+
+/// Builds the two main diagonals of `board` as [`UniqueGroup`] constraints
+/// (the "X-Sudoku" variant).
+pub fn diagonal_groups(board: &Board) -> Vec<Rc<dyn Constraint>> {
+    let side = board.side();
+    let main: Vec<usize> = (0..side).map(|i| i * side + i).collect();
+    let anti: Vec<usize> = (0..side).map(|i| i * side + (side - 1 - i)).collect();
+    vec![
+        Rc::new(UniqueGroup::new(main)),
+        Rc::new(UniqueGroup::new(anti)),
+    ]
+}
+
+/// Builds the four extra 3x3 regions of the classic 9x9 Windoku variant.
+/// Only defined for `order == 3`; returns an empty list for any other
+/// order, since Windoku has no standard generalization to other sizes.
+pub fn windoku_groups(board: &Board) -> Vec<Rc<dyn Constraint>> {
+    if board.order != 3 {
+        return Vec::new();
+    }
+    let side = board.side();
+    let mut groups = Vec::with_capacity(4);
+    for &start_row in &[1usize, 5] {
+        for &start_col in &[1usize, 5] {
+            let mut cells = Vec::with_capacity(9);
+            for r in 0..3 {
+                for c in 0..3 {
+                    cells.push((start_row + r) * side + (start_col + c));
+                }
+            }
+            groups.push(Rc::new(UniqueGroup::new(cells)) as Rc<dyn Constraint>);
+        }
+    }
+    groups
+}
+
+/// Wraps each `(cells, target_sum)` pair into a [`KillerCage`] constraint.
+pub fn killer_cages(cages: Vec<(Vec<usize>, u32)>) -> Vec<Rc<dyn Constraint>> {
+    cages
+        .into_iter()
+        .map(|(cells, target_sum)| Rc::new(KillerCage::new(cells, target_sum)) as Rc<dyn Constraint>)
+        .collect()
+}
@@ -17,10 +17,13 @@
 */
 
 use crate::board::Board;
+use crate::constraints::{self, Constraint};
 use crate::logical_solver::{self, TechniqueLevel};
 use crate::solver;
-use rand::rng;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
+use rand::{rng, Rng, SeedableRng};
+use std::rc::Rc;
 
 /// Represents the target difficulty of the generated puzzle.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -31,22 +34,55 @@ pub enum Difficulty {
     Extreme,
 }
 
-/// Generate a complete, solved Sudoku board.
-fn generate_full_solution() -> Board {
-    let mut board = Board { cells: [0; 81] };
-    let mut numbers: [u8; 9] = [1, 2, 3, 4, 5, 6, 7, 8, 9];
-    numbers.shuffle(&mut rng());
+/// Selects which extra rule constraints (if any) a generated board enforces
+/// on top of the classic row/column/box rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// Plain Sudoku: row, column and box rules only.
+    Classic,
+    /// "X-Sudoku": both main diagonals must also contain every digit once.
+    Diagonal,
+    /// Windoku: four extra 3x3 regions must also contain every digit once.
+    /// Only defined for the classic 9x9 (`order == 3`) board.
+    Windoku,
+}
+
+fn constraints_for(board: &Board, variant: Variant) -> Vec<Rc<dyn Constraint>> {
+    match variant {
+        Variant::Classic => Vec::new(),
+        Variant::Diagonal => constraints::diagonal_groups(board),
+        Variant::Windoku => constraints::windoku_groups(board),
+    }
+}
+
+/// Generate a complete, solved Sudoku board of the given `order`.
+fn generate_full_solution<R: Rng + ?Sized>(order: u8, rng: &mut R) -> Board {
+    generate_full_solution_with_constraints(order, Vec::new(), rng)
+}
+
+/// Generate a complete, solved board of the given `order`, enforcing the
+/// extra `constraints` (if any) alongside the usual row/column/box rules.
+fn generate_full_solution_with_constraints<R: Rng + ?Sized>(
+    order: u8,
+    constraints: Vec<Rc<dyn Constraint>>,
+    rng: &mut R,
+) -> Board {
+    let mut board = Board::empty(order);
+    board.constraints = constraints;
+    let mut numbers: Vec<u8> = (1..=board.side() as u8).collect();
+    numbers.shuffle(rng);
     solver::solve_randomized(&mut board, &numbers);
     board
 }
 
 /// Attempts to generate an Easy or Medium puzzle from a given solution.
-fn generate_easy_medium(solution: &Board, min_clues: usize) -> Board {
-    let mut puzzle = *solution;
-    let mut indices: Vec<usize> = (0..81).collect();
-    indices.shuffle(&mut rng());
+fn generate_easy_medium<R: Rng + ?Sized>(solution: &Board, min_clues: usize, rng: &mut R) -> Board {
+    let mut puzzle = solution.clone();
+    let cell_count = puzzle.cells.len();
+    let mut indices: Vec<usize> = (0..cell_count).collect();
+    indices.shuffle(rng);
 
-    let mut current_clues = 81;
+    let mut current_clues = cell_count;
 
     for index in indices {
         if current_clues <= min_clues {
@@ -54,11 +90,11 @@ fn generate_easy_medium(solution: &Board, min_clues: usize) -> Board {
         }
 
         let original_value = puzzle.cells[index];
-        puzzle.cells[index] = 0;
+        puzzle.set(index, 0);
 
         // Ensure the puzzle still has a unique solution.
         if solver::count_solutions(&puzzle) != 1 {
-            puzzle.cells[index] = original_value;
+            puzzle.set(index, original_value);
             continue;
         }
 
@@ -68,34 +104,39 @@ fn generate_easy_medium(solution: &Board, min_clues: usize) -> Board {
             current_clues -= 1;
         } else {
             // This removal made the puzzle too hard, revert it.
-            puzzle.cells[index] = original_value;
+            puzzle.set(index, original_value);
         }
     }
     puzzle
 }
 
 /// Creates a "minimal" puzzle from a solution by removing as many clues as possible while maintaining a unique solution.
-fn create_minimal_puzzle(solution: &Board) -> Board {
-    let mut puzzle = *solution;
-    let mut indices: Vec<usize> = (0..81).collect();
-    indices.shuffle(&mut rng());
+fn create_minimal_puzzle<R: Rng + ?Sized>(solution: &Board, rng: &mut R) -> Board {
+    let mut puzzle = solution.clone();
+    let mut indices: Vec<usize> = (0..puzzle.cells.len()).collect();
+    indices.shuffle(rng);
 
     for index in indices {
         let original_value = puzzle.cells[index];
-        puzzle.cells[index] = 0;
+        puzzle.set(index, 0);
         if solver::count_solutions(&puzzle) != 1 {
-            puzzle.cells[index] = original_value;
+            puzzle.set(index, original_value);
         }
     }
     puzzle
 }
 
 /// Creates a minimal puzzle optimized for hard/extreme generation.
-fn create_minimal_puzzle_with_limit(solution: &Board, min_clues: usize) -> Board {
-    let mut puzzle = *solution;
-    let mut indices: Vec<usize> = (0..81).collect();
-    indices.shuffle(&mut rng());
-    let mut clues_remaining = 81;
+fn create_minimal_puzzle_with_limit<R: Rng + ?Sized>(
+    solution: &Board,
+    min_clues: usize,
+    rng: &mut R,
+) -> Board {
+    let mut puzzle = solution.clone();
+    let cell_count = puzzle.cells.len();
+    let mut indices: Vec<usize> = (0..cell_count).collect();
+    indices.shuffle(rng);
+    let mut clues_remaining = cell_count;
 
     for index in indices {
         // Early exit when reaching the minimum clue threshold.
@@ -104,9 +145,9 @@ fn create_minimal_puzzle_with_limit(solution: &Board, min_clues: usize) -> Board
         }
 
         let original_value = puzzle.cells[index];
-        puzzle.cells[index] = 0;
+        puzzle.set(index, 0);
         if solver::count_solutions(&puzzle) != 1 {
-            puzzle.cells[index] = original_value;
+            puzzle.set(index, original_value);
         } else {
             clues_remaining -= 1;
         }
@@ -114,36 +155,91 @@ fn create_minimal_puzzle_with_limit(solution: &Board, min_clues: usize) -> Board
     puzzle
 }
 
-/// Generates a puzzle of a specific difficulty.
+/// Generates a puzzle of a specific difficulty, using the classic 9x9 (order 3) board.
 pub fn generate(difficulty: Difficulty) -> Board {
+    generate_for_order(3, difficulty)
+}
+
+/// Generates a puzzle of a specific difficulty for the given box `order`
+/// (e.g. `2` for 4x4, `3` for 9x9, `4` for 16x16, `5` for 25x25).
+pub fn generate_for_order(order: u8, difficulty: Difficulty) -> Board {
+    generate_for_order_with_rng(order, difficulty, &mut rng())
+}
+
+/// Generates a classic 9x9 puzzle of the given `difficulty` from a seeded
+/// PRNG instead of system randomness, so the exact same puzzle can be
+/// reproduced later by reusing the returned seed. Returns the puzzle along
+/// with the effective seed (the one passed in), letting callers that want a
+/// fresh puzzle generate their own seed and persist it for replay.
+pub fn generate_with_seed(difficulty: Difficulty, seed: u64) -> (Board, u64) {
+    let mut seeded_rng = StdRng::seed_from_u64(seed);
+    let board = generate_for_order_with_rng(3, difficulty, &mut seeded_rng);
+    (board, seed)
+}
+
+/// 9x9-baseline [`logical_solver::difficulty_score`] bands for
+/// [`Difficulty::Hard`] and [`Difficulty::Extreme`], scaled like the
+/// clue-count thresholds below. A puzzle merely reaching a `TechniqueLevel`
+/// can still be trivial or brutal within that level (two puzzles both
+/// topping out at `Advanced` can need very different amounts of
+/// advanced-technique work), so these bands give `generate_for_order` a
+/// finer and more reliable Hard/Extreme split than the level enum alone.
+const HARD_MIN_SCORE: usize = 30;
+const HARD_MAX_SCORE: usize = 200;
+const EXTREME_MIN_SCORE: usize = 80;
+
+/// Shared implementation backing [`generate_for_order`] and
+/// [`generate_with_seed`]: every draw of randomness, including retries after
+/// a rejected candidate, comes from the same `rng` so the output is fully
+/// determined by its initial state.
+fn generate_for_order_with_rng<R: Rng + ?Sized>(
+    order: u8,
+    difficulty: Difficulty,
+    rng: &mut R,
+) -> Board {
+    // Clue-count and score thresholds below were calibrated for the classic
+    // 9x9 (81 cell) board; scale them proportionally for other orders.
+    let cell_count = order as usize * order as usize * order as usize * order as usize;
+    let scale = |clues_9x9: usize| clues_9x9 * cell_count / 81;
+
     loop {
-        let solution = generate_full_solution();
+        let solution = generate_full_solution(order, rng);
 
         let puzzle_candidate = match difficulty {
-            Difficulty::Easy => Some(generate_easy_medium(&solution, 40)),
-            Difficulty::Medium => Some(generate_easy_medium(&solution, 32)),
+            Difficulty::Easy => Some(generate_easy_medium(&solution, scale(40), rng)),
+            Difficulty::Medium => Some(generate_easy_medium(&solution, scale(32), rng)),
             Difficulty::Hard => {
-                let minimal_puzzle = create_minimal_puzzle_with_limit(&solution, 22);
+                let minimal_puzzle = create_minimal_puzzle_with_limit(&solution, scale(22), rng);
                 let (level, solved_board) = logical_solver::get_difficulty(&minimal_puzzle);
+                let score = logical_solver::difficulty_score(&minimal_puzzle) as usize;
 
-                // Hard puzzles should use intermediate techniques.
-                if level == TechniqueLevel::Intermediate && is_solved(&solved_board) {
+                // Hard puzzles should use intermediate techniques, and fall
+                // within the hard-tier score band rather than being accepted
+                // as soon as they merely reach `Intermediate`.
+                if level == TechniqueLevel::Intermediate
+                    && is_solved(&solved_board)
+                    && (scale(HARD_MIN_SCORE)..=scale(HARD_MAX_SCORE)).contains(&score)
+                {
                     Some(minimal_puzzle)
                 } else {
                     None // Discard and retry with a new seed.
                 }
             }
             Difficulty::Extreme => {
-                let minimal_puzzle = create_minimal_puzzle(&solution);
+                let minimal_puzzle = create_minimal_puzzle(&solution, rng);
                 let clues_count = minimal_puzzle.cells.iter().filter(|&&c| c != 0).count();
-                if clues_count < 17 || clues_count > 35 {
+                if clues_count < scale(17) || clues_count > scale(35) {
                     None
                 } else {
                     let (_level, solved_board) = logical_solver::get_difficulty(&minimal_puzzle);
+                    let score = logical_solver::difficulty_score(&minimal_puzzle) as usize;
 
-                    // Extreme puzzles should not be completely solvable by basic/intermediate techniques.
+                    // Extreme puzzles should not be completely solvable by
+                    // the logical solver's techniques, and should clear the
+                    // extreme-tier score floor so a puzzle that just barely
+                    // escapes full resolution doesn't count as Extreme.
                     let is_completely_solved = solved_board.cells.iter().all(|&c| c != 0);
-                    if !is_completely_solved {
+                    if !is_completely_solved && score >= scale(EXTREME_MIN_SCORE) {
                         Some(minimal_puzzle)
                     } else {
                         None // Discard and retry with a new seed.
@@ -173,3 +269,132 @@ pub fn generate(difficulty: Difficulty) -> Board {
 fn is_solved(board: &Board) -> bool {
     board.cells.iter().all(|&cell| cell != 0)
 }
+
+/// Generates a classic 9x9 puzzle calibrated to a specific [`TechniqueLevel`]
+/// from a seeded PRNG, returning the puzzle together with its rated level.
+///
+/// Unlike [`generate_for_order`], which buckets puzzles into the coarse
+/// `Difficulty` enum via fixed clue-count targets, this digs holes one at a
+/// time (in symmetric pairs) directly against [`logical_solver::get_difficulty`]
+/// until it lands exactly on `target`, backing off any dig that overshoots
+/// it. If no sequence of digs on a given solution reaches `target` exactly,
+/// a fresh solution is tried.
+pub fn generate_to_level(target: TechniqueLevel, seed: u64) -> (Board, TechniqueLevel) {
+    let mut seeded_rng = StdRng::seed_from_u64(seed);
+    loop {
+        let solution = generate_full_solution(3, &mut seeded_rng);
+        if let Some(result) = dig_to_level(&solution, target, &mut seeded_rng) {
+            return result;
+        }
+    }
+}
+
+/// Dig holes in `solution` one symmetric pair at a time, in a random order,
+/// rejecting any dig that makes the solution non-unique. Stops and returns
+/// as soon as [`logical_solver::get_difficulty`] reports exactly `target`;
+/// a dig that overshoots `target` is backed off and the next candidate cell
+/// is tried instead. Returns `None` if every cell has been tried without
+/// ever landing exactly on `target`.
+fn dig_to_level<R: Rng + ?Sized>(
+    solution: &Board,
+    target: TechniqueLevel,
+    rng: &mut R,
+) -> Option<(Board, TechniqueLevel)> {
+    let mut puzzle = solution.clone();
+    let cell_count = puzzle.cells.len();
+    let mut indices: Vec<usize> = (0..cell_count).collect();
+    indices.shuffle(rng);
+
+    for index in indices {
+        if puzzle.cells[index] == 0 {
+            continue; // Already removed as an earlier dig's symmetric partner.
+        }
+        let partner = cell_count - 1 - index;
+
+        let original_value = puzzle.cells[index];
+        let partner_original_value = puzzle.cells[partner];
+        puzzle.set(index, 0);
+        if partner != index {
+            puzzle.set(partner, 0);
+        }
+
+        if solver::count_solutions(&puzzle) != 1 {
+            puzzle.set(index, original_value);
+            puzzle.set(partner, partner_original_value);
+            continue;
+        }
+
+        let (level, _) = logical_solver::get_difficulty(&puzzle);
+        if level > target {
+            // This dig made the puzzle harder than requested; back it off
+            // and keep digging elsewhere.
+            puzzle.set(index, original_value);
+            puzzle.set(partner, partner_original_value);
+            continue;
+        }
+
+        if level == target {
+            return Some((puzzle, level));
+        }
+        // Still easier than the target; keep digging with the next cell.
+    }
+    None
+}
+
+/// Generates a puzzle for the given `order` whose cumulative numeric
+/// difficulty score (see [`logical_solver::difficulty_score`]) falls within
+/// `min_score..=max_score`.
+///
+/// This is the same score-banding [`generate_for_order`] uses internally to
+/// split `Hard` and `Extreme` more reliably than the coarse
+/// [`TechniqueLevel`] gate alone, exposed directly for callers that want a
+/// custom band instead of one of the fixed [`Difficulty`] tiers.
+pub fn generate_scored(order: u8, min_score: u32, max_score: u32) -> Board {
+    let mut rng = rng();
+    loop {
+        let solution = generate_full_solution(order, &mut rng);
+        let minimal_puzzle = create_minimal_puzzle(&solution, &mut rng);
+        let score = logical_solver::difficulty_score(&minimal_puzzle);
+
+        if score >= min_score && score <= max_score {
+            return minimal_puzzle;
+        }
+        // Score outside the target band; discard and retry with a new solution.
+    }
+}
+
+/// Generates a puzzle of a specific `difficulty` and `order` under an extra
+/// rule `variant` (diagonal, Windoku, ...), with a unique solution once that
+/// variant's constraints are taken into account.
+///
+/// `logical_solver`'s difficulty gating only understands the classic
+/// row/column/box rules, so for `Variant::Classic` this matches
+/// [`generate_for_order`] exactly; for any other variant, Hard and Extreme
+/// both fall back to a maximally-reduced puzzle rather than the finer
+/// `TechniqueLevel` gate, since that gate cannot see the extra constraint.
+pub fn generate_variant(order: u8, difficulty: Difficulty, variant: Variant) -> Board {
+    if variant == Variant::Classic {
+        return generate_for_order(order, difficulty);
+    }
+
+    let mut rng = rng();
+    let cell_count = order as usize * order as usize * order as usize * order as usize;
+    let scale = |clues_9x9: usize| clues_9x9 * cell_count / 81;
+
+    loop {
+        let constraints = constraints_for(&Board::empty(order), variant);
+        let solution = generate_full_solution_with_constraints(order, constraints, &mut rng);
+
+        let puzzle = match difficulty {
+            Difficulty::Easy => generate_easy_medium(&solution, scale(40), &mut rng),
+            Difficulty::Medium => generate_easy_medium(&solution, scale(32), &mut rng),
+            Difficulty::Hard | Difficulty::Extreme => create_minimal_puzzle(&solution, &mut rng),
+        };
+
+        if solver::count_solutions(&puzzle) == 1 {
+            return puzzle;
+        }
+        // Uniqueness broke down (possible for Easy/Medium's partial removal
+        // under an unfamiliar variant); discard and retry with a new solution.
+    }
+}
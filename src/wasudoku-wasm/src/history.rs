@@ -0,0 +1,119 @@
+/*
+* Copyright (C) 2025  Henrique Almeida
+* This file is part of WASudoku.
+
+* WASudoku is free software: you can redistribute it and/or modify
+* it under the terms of the GNU Affero General Public License as published
+* by the Free Software Foundation, either version 3 of the License, or
+* (at your option) any later version.
+
+* WASudoku is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+* GNU Affero General Public License for more details.
+
+* You should have received a copy of the GNU Affero General Public License
+* along with WASudoku.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! A stack-based undo/redo move history for interactive play, gated by
+//! [`Board::is_valid_move`] so a conflicting placement is reported rather
+//! than silently applied.
+
+use crate::board::Board;
+
+/// A single recorded placement: the cell `index`, its value before the
+/// move, and the value placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Move {
+    index: usize,
+    old_value: u8,
+    new_value: u8,
+}
+
+/// An undo/redo history of moves applied to a [`Board`].
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    undo_stack: Vec<Move>,
+    redo_stack: Vec<Move>,
+}
+
+impl History {
+    /// Create an empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attempt to place `value` at `index` on `board` (`value = 0` clears
+    /// the cell and is always allowed). The cell is cleared before checking
+    /// [`Board::is_valid_move`], so re-placing the same digit a cell already
+    /// holds does not spuriously conflict with itself.
+    ///
+    /// Recording a new move clears the redo stack, since redoing past it
+    /// would no longer apply to the board it was originally recorded against.
+    ///
+    /// ### Returns
+    ///
+    /// `true` if the move was applied and pushed onto the undo stack,
+    /// `false` if `index` is out of range or the move conflicts with the
+    /// board's current rules and was left unapplied.
+    pub fn apply_move(&mut self, board: &mut Board, index: usize, value: u8) -> bool {
+        if index >= board.cells.len() {
+            return false;
+        }
+
+        let side = board.side();
+        let row = index / side;
+        let col = index % side;
+        let old_value = board.cells[index];
+
+        board.set(index, 0);
+        if value != 0 && !board.is_valid_move(row, col, value) {
+            board.set(index, old_value);
+            return false;
+        }
+
+        board.set(index, value);
+        self.undo_stack.push(Move {
+            index,
+            old_value,
+            new_value: value,
+        });
+        self.redo_stack.clear();
+        true
+    }
+
+    /// Undo the most recent move, restoring its prior value and moving it
+    /// onto the redo stack.
+    ///
+    /// ### Returns
+    ///
+    /// `true` if a move was undone, `false` if the undo stack was empty.
+    pub fn undo(&mut self, board: &mut Board) -> bool {
+        match self.undo_stack.pop() {
+            Some(mv) => {
+                board.set(mv.index, mv.old_value);
+                self.redo_stack.push(mv);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Redo the most recently undone move, moving it back onto the undo
+    /// stack.
+    ///
+    /// ### Returns
+    ///
+    /// `true` if a move was redone, `false` if the redo stack was empty.
+    pub fn redo(&mut self, board: &mut Board) -> bool {
+        match self.redo_stack.pop() {
+            Some(mv) => {
+                board.set(mv.index, mv.new_value);
+                self.undo_stack.push(mv);
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,158 @@
+/*
+* Copyright (C) 2025  Henrique Almeida
+* This file is part of WASudoku.
+
+* WASudoku is free software: you can redistribute it and/or modify
+* it under the terms of the GNU Affero General Public License as published
+* by the Free Software Foundation, either version 3 of the License, or
+* (at your option) any later version.
+
+* WASudoku is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+* GNU Affero General Public License for more details.
+
+* You should have received a copy of the GNU Affero General Public License
+* along with WASudoku.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Import and export of the KSudoku puzzle file representation, so boards
+//! can interoperate with KSudoku-format puzzles.
+
+use crate::board::Board;
+
+/// A parsed KSudoku puzzle, as read from or written to the KSudoku file
+/// representation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KsudokuPuzzle {
+    /// The KSudoku game variant (only `"Plain"` is currently supported).
+    pub puzzle_type: String,
+    /// The puzzle's side length, as used by KSudoku (`9`, `16`, or `25`).
+    pub order: u8,
+    /// The encoded puzzle string (`_` for blank, `b` = 1, `c` = 2, ...).
+    pub puzzle: String,
+    /// The encoded solution string, if one was embedded in the file.
+    pub solution: Option<String>,
+}
+
+/// Decode a single KSudoku character into a cell value.
+///
+/// `_` is blank, and digits start at `b` (`value = ch - 'a'`) so values
+/// above 9 are representable without a two-character encoding.
+fn decode_char(ch: char) -> Option<u8> {
+    match ch {
+        '_' => Some(0),
+        'a'..='z' => Some(ch as u8 - b'a'),
+        _ => None,
+    }
+}
+
+/// Encode a cell value back into its KSudoku character.
+fn encode_value(value: u8) -> char {
+    if value == 0 {
+        '_'
+    } else {
+        (b'a' + value) as char
+    }
+}
+
+/// Decode a KSudoku puzzle string into board cells.
+fn decode_cells(s: &str) -> Result<Vec<u8>, String> {
+    s.chars()
+        .enumerate()
+        .map(|(i, ch)| {
+            decode_char(ch)
+                .ok_or_else(|| format!("Invalid KSudoku character '{}' at index {}", ch, i))
+        })
+        .collect()
+}
+
+/// Parse a line-based KSudoku puzzle file:
+///
+/// ```text
+/// Type: Plain
+/// Order: 9
+/// Puzzle: _bc...
+/// Solution: dbc...
+/// ```
+///
+/// `Solution` is optional.
+fn parse_fields(s: &str) -> Result<KsudokuPuzzle, String> {
+    let mut puzzle_type = None;
+    let mut order = None;
+    let mut puzzle = None;
+    let mut solution = None;
+
+    for line in s.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (key, value) = line
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid KSudoku line (expected 'Key: value'): {}", line))?;
+        let value = value.trim();
+
+        match key.trim().to_ascii_lowercase().as_str() {
+            "type" => puzzle_type = Some(value.to_string()),
+            "order" => {
+                order = Some(
+                    value
+                        .parse::<u8>()
+                        .map_err(|_| format!("Invalid KSudoku order: {}", value))?,
+                )
+            }
+            "puzzle" => puzzle = Some(value.to_string()),
+            "solution" => solution = Some(value.to_string()),
+            other => return Err(format!("Unknown KSudoku field: {}", other)),
+        }
+    }
+
+    Ok(KsudokuPuzzle {
+        puzzle_type: puzzle_type.unwrap_or_else(|| "Plain".to_string()),
+        order: order.ok_or_else(|| String::from("Missing KSudoku 'Order' field"))?,
+        puzzle: puzzle.ok_or_else(|| String::from("Missing KSudoku 'Puzzle' field"))?,
+        solution,
+    })
+}
+
+impl Board {
+    /// Parse a KSudoku-format puzzle file into a `Board`, and its embedded
+    /// solution `Board` if one was present.
+    ///
+    /// ### Errors
+    ///
+    /// Returns an `Err` if the file is malformed, contains invalid
+    /// characters, or describes a board with initial conflicts.
+    pub fn from_ksudoku(s: &str) -> Result<(Board, Option<Board>), String> {
+        let parsed = parse_fields(s)?;
+
+        let box_order = Board::order_for_len((parsed.order as usize) * (parsed.order as usize))?;
+        let puzzle_cells = decode_cells(&parsed.puzzle)?;
+        let puzzle_board = Board::from_cells(box_order, puzzle_cells)?;
+
+        let solution_board = match &parsed.solution {
+            Some(solution) => {
+                let solution_cells = decode_cells(solution)?;
+                Some(Board::from_cells(box_order, solution_cells)?)
+            }
+            None => None,
+        };
+
+        Ok((puzzle_board, solution_board))
+    }
+
+    /// Serialize this board into the KSudoku file representation, embedding
+    /// `solution`'s cells as the `Solution` field if one is given, so the
+    /// front end can cache a solved board alongside the puzzle and reload it
+    /// later without re-solving.
+    pub fn to_ksudoku(&self, order: u8, solution: Option<&Board>) -> String {
+        let puzzle: String = self.cells.iter().map(|&v| encode_value(v)).collect();
+        let mut file = format!("Type: Plain\nOrder: {}\nPuzzle: {}\n", order, puzzle);
+        if let Some(solution) = solution {
+            let solution: String = solution.cells.iter().map(|&v| encode_value(v)).collect();
+            file.push_str(&format!("Solution: {}\n", solution));
+        }
+        file
+    }
+}
@@ -17,18 +17,34 @@
 */
 
 pub mod board;
+pub mod constraints;
 pub mod generate;
+pub mod history;
+pub mod ksudoku;
 pub mod logical_solver;
+pub mod proof;
+pub mod sat_solver;
 pub mod solver;
 pub mod types;
 mod utils;
 
-use board::Board;
-use generate::Difficulty;
-use std::panic;
-use types::SolveResult;
+use board::{Board, MAX_ORDER, MIN_ORDER};
+use generate::{Difficulty, Variant};
+use history::History;
+use solver::SolveError;
+use std::panic::{self, AssertUnwindSafe};
+use types::{KsudokuLoadResult, MoveResult, SolveResult};
 use wasm_bindgen::prelude::*;
 
+/// Which backend finishes a puzzle once logical techniques stall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Engine {
+    /// MRV backtracking (`logical_solver::solve_with_steps_and_guesses`).
+    Backtracking,
+    /// The CDCL SAT backend (`sat_solver::cdcl_solve`).
+    Sat,
+}
+
 /// Set the panic hook to forward Rust panics to the browser console.
 #[wasm_bindgen(start)]
 pub fn main() {
@@ -39,13 +55,17 @@ pub fn main() {
 ///
 /// This function employs a hybrid strategy. It first applies logical solving
 /// techniques to generate human-readable steps. If logic alone cannot solve
-/// the puzzle, it falls back to a high-speed backtracking algorithm to find
-/// the final solution.
+/// the puzzle, it falls back to the selected engine: the backtracking
+/// engine keeps recording steps (as `"Guess"`) so the full path can still
+/// be replayed, while the SAT engine only reports the final solution.
 ///
 /// ### Arguments
 ///
 /// * `board_str` - An 81-character string representing the Sudoku board,
 ///   with `.` or `0` for empty cells.
+/// * `engine_str` - Which backend finishes the puzzle once logical
+///   techniques stall: "backtracking" (MRV backtracking) or "sat" (the
+///   CDCL SAT backend).
 ///
 /// ### Returns
 ///
@@ -54,20 +74,49 @@ pub fn main() {
 ///
 /// ### Errors
 ///
-/// * A `JsValue` error if the input is invalid, the puzzle is unsolvable,
-///   or a panic occurs in the underlying solver.
+/// * A `JsValue` error if the input or engine name is invalid, the puzzle
+///   is unsolvable, or a panic occurs in the underlying solver.
 #[wasm_bindgen]
-pub fn solve_sudoku(board_str: &str) -> Result<JsValue, JsValue> {
+pub fn solve_sudoku(board_str: &str, engine_str: &str) -> Result<JsValue, JsValue> {
     let initial_board = Board::from_str(board_str).map_err(|e| JsValue::from_str(&e))?;
 
+    let engine = match engine_str {
+        "backtracking" => Engine::Backtracking,
+        "sat" => Engine::Sat,
+        _ => return Err(JsValue::from_str("Invalid solver engine.")),
+    };
+
     // Use `catch_unwind` to contain any panics within the solver logic,
     // preventing the WASM module from crashing and allowing a graceful error return.
-    let solve_result = panic::catch_unwind(move || {
-        let (steps, mut board_after_logic) = logical_solver::solve_with_steps(&initial_board);
+    //
+    // `Board` holds `Rc<dyn Constraint>`, which isn't `RefUnwindSafe`, but
+    // constraints are read-only predicates with no interior mutability, so
+    // there's nothing for a panic to observe half-mutated; asserting
+    // unwind-safety here is sound.
+    let solve_result = panic::catch_unwind(AssertUnwindSafe(move || {
+        // The backtracking engine replays its guesses as `"Guess"` steps, so
+        // it needs its own full solve pass rather than picking up after
+        // `solve_with_steps`.
+        let (steps, mut board_after_logic) = match engine {
+            Engine::Backtracking => logical_solver::solve_with_steps_and_guesses(&initial_board),
+            Engine::Sat => logical_solver::solve_with_steps(&initial_board),
+        };
 
-        // If logic was not sufficient, fall back to the backtracking algorithm.
+        // If logic (and, for backtracking, guessing) was not sufficient,
+        // fall back to the selected engine.
         let final_solution = if board_after_logic.cells.contains(&0) {
-            if solver::solve(&mut board_after_logic) {
+            let solved = match engine {
+                Engine::Backtracking => false,
+                Engine::Sat => match sat_solver::cdcl_solve(&board_after_logic) {
+                    Some(solution) => {
+                        board_after_logic = solution;
+                        true
+                    }
+                    None => false,
+                },
+            };
+
+            if solved {
                 Some(board_after_logic.to_string())
             } else {
                 return None;
@@ -80,7 +129,7 @@ pub fn solve_sudoku(board_str: &str) -> Result<JsValue, JsValue> {
             steps,
             solution: final_solution,
         })
-    });
+    }));
 
     match solve_result {
         Ok(Some(result)) => Ok(serde_wasm_bindgen::to_value(&result).unwrap()),
@@ -95,17 +144,19 @@ pub fn solve_sudoku(board_str: &str) -> Result<JsValue, JsValue> {
 ///
 /// * `difficulty_str` - A string representing the desired difficulty:
 ///   "easy", "medium", "hard", or "extreme".
+/// * `order` - The box side length: `2` for 4x4, `3` for the classic 9x9,
+///   `4` for 16x16, or `5` for 25x25.
 ///
 /// ### Returns
 ///
-/// * A `String` containing the 81-character puzzle.
+/// * A `String` containing the `(order^2)^2`-character puzzle.
 ///
 /// ### Errors
 ///
-/// * A `JsValue` error if the difficulty string is invalid or if the
-///   generator panics.
+/// * A `JsValue` error if the difficulty string or order is invalid, or if
+///   the generator panics.
 #[wasm_bindgen]
-pub fn generate_sudoku(difficulty_str: &str) -> Result<String, JsValue> {
+pub fn generate_sudoku(difficulty_str: &str, order: u8) -> Result<String, JsValue> {
     let difficulty = match difficulty_str {
         "easy" => Difficulty::Easy,
         "medium" => Difficulty::Medium,
@@ -114,7 +165,14 @@ pub fn generate_sudoku(difficulty_str: &str) -> Result<String, JsValue> {
         _ => return Err(JsValue::from_str("Invalid difficulty level.")),
     };
 
-    let result = panic::catch_unwind(|| generate::generate(difficulty));
+    if !(MIN_ORDER..=MAX_ORDER).contains(&order) {
+        return Err(JsValue::from_str(&format!(
+            "Invalid board order: {} (expected {}-{}).",
+            order, MIN_ORDER, MAX_ORDER
+        )));
+    }
+
+    let result = panic::catch_unwind(|| generate::generate_for_order(order, difficulty));
 
     match result {
         Ok(board) => Ok(board.to_string()),
@@ -123,3 +181,256 @@ pub fn generate_sudoku(difficulty_str: &str) -> Result<String, JsValue> {
         )),
     }
 }
+
+/// Generate a new Sudoku puzzle under an extra rule variant (diagonal,
+/// Windoku, ...), with a unique solution once that variant's constraints
+/// are taken into account.
+///
+/// ### Arguments
+///
+/// * `difficulty_str` - A string representing the desired difficulty:
+///   "easy", "medium", "hard", or "extreme".
+/// * `order` - The box side length: `2` for 4x4, `3` for the classic 9x9,
+///   `4` for 16x16, or `5` for 25x25.
+/// * `variant_str` - The rule variant: "classic", "diagonal", or "windoku".
+///
+/// ### Returns
+///
+/// * A `String` containing the `(order^2)^2`-character puzzle.
+///
+/// ### Errors
+///
+/// * A `JsValue` error if the difficulty, order, or variant string is
+///   invalid, or if the generator panics.
+#[wasm_bindgen]
+pub fn generate_variant_sudoku(
+    difficulty_str: &str,
+    order: u8,
+    variant_str: &str,
+) -> Result<String, JsValue> {
+    let difficulty = match difficulty_str {
+        "easy" => Difficulty::Easy,
+        "medium" => Difficulty::Medium,
+        "hard" => Difficulty::Hard,
+        "extreme" => Difficulty::Extreme,
+        _ => return Err(JsValue::from_str("Invalid difficulty level.")),
+    };
+
+    let variant = match variant_str {
+        "classic" => Variant::Classic,
+        "diagonal" => Variant::Diagonal,
+        "windoku" => Variant::Windoku,
+        _ => return Err(JsValue::from_str("Invalid rule variant.")),
+    };
+
+    if !(MIN_ORDER..=MAX_ORDER).contains(&order) {
+        return Err(JsValue::from_str(&format!(
+            "Invalid board order: {} (expected {}-{}).",
+            order, MIN_ORDER, MAX_ORDER
+        )));
+    }
+
+    let result = panic::catch_unwind(|| generate::generate_variant(order, difficulty, variant));
+
+    match result {
+        Ok(board) => Ok(board.to_string()),
+        Err(_) => Err(JsValue::from_str(
+            "Generator crashed due to a critical error.",
+        )),
+    }
+}
+
+/// Load a `.ksudoku` file's contents into a puzzle board (and its embedded
+/// solution, if the file had one), so the front end doesn't need to re-solve
+/// a puzzle it already has a cached solution for.
+///
+/// ### Arguments
+///
+/// * `ksudoku_str` - The contents of a KSudoku-format puzzle file.
+///
+/// ### Returns
+///
+/// * A `JsValue` containing the serialized `KsudokuLoadResult`.
+///
+/// ### Errors
+///
+/// * A `JsValue` error if the file is malformed or describes a board with
+///   initial conflicts.
+#[wasm_bindgen]
+pub fn import_ksudoku(ksudoku_str: &str) -> Result<JsValue, JsValue> {
+    // See the `catch_unwind` in `solve_sudoku` above for why asserting
+    // unwind-safety on a `Board` carrying `Rc<dyn Constraint>` is sound.
+    let import_result = panic::catch_unwind(AssertUnwindSafe(|| Board::from_ksudoku(ksudoku_str)));
+
+    match import_result {
+        Ok(Ok((puzzle, solution))) => {
+            let result = KsudokuLoadResult {
+                puzzle: puzzle.to_string(),
+                solution: solution.map(|board| board.to_string()),
+            };
+            Ok(serde_wasm_bindgen::to_value(&result).unwrap())
+        }
+        Ok(Err(e)) => Err(JsValue::from_str(&e)),
+        Err(_) => Err(JsValue::from_str(
+            "KSudoku import crashed due to a critical error.",
+        )),
+    }
+}
+
+/// Save a puzzle board (and an optional cached solution) as a `.ksudoku`
+/// file's contents.
+///
+/// ### Arguments
+///
+/// * `board_str` - The puzzle board as an encoded string.
+/// * `solution_str` - The solution board as an encoded string, to embed
+///   alongside the puzzle, or `None` to omit it.
+///
+/// ### Returns
+///
+/// * A `String` containing the KSudoku file's contents.
+///
+/// ### Errors
+///
+/// * A `JsValue` error if `board_str` or `solution_str` is invalid.
+#[wasm_bindgen]
+pub fn export_ksudoku(board_str: &str, solution_str: Option<String>) -> Result<String, JsValue> {
+    let board = Board::from_str(board_str).map_err(|e| JsValue::from_str(&e))?;
+    let solution_board = solution_str
+        .map(|s| Board::from_str(&s))
+        .transpose()
+        .map_err(|e| JsValue::from_str(&e))?;
+
+    // See the `catch_unwind` in `solve_sudoku` above for why asserting
+    // unwind-safety on a `Board` carrying `Rc<dyn Constraint>` is sound.
+    match panic::catch_unwind(AssertUnwindSafe(|| {
+        board.to_ksudoku(board.side() as u8, solution_board.as_ref())
+    })) {
+        Ok(file) => Ok(file),
+        Err(_) => Err(JsValue::from_str(
+            "KSudoku export crashed due to a critical error.",
+        )),
+    }
+}
+
+/// Solve a Sudoku puzzle, precisely distinguishing an unsolvable grid from
+/// one with more than one solution, instead of collapsing every failure
+/// into a single generic error.
+///
+/// ### Arguments
+///
+/// * `board_str` - An encoded board string (16, 81, 256 or 625 characters),
+///   with `.` or `0` for empty cells.
+///
+/// ### Returns
+///
+/// * The unique solution as an encoded string.
+///
+/// ### Errors
+///
+/// * A `JsValue` error describing the specific `SolveError` outcome
+///   (malformed input, unsolvable, ambiguous, or a solver panic).
+#[wasm_bindgen]
+pub fn solve_sudoku_checked(board_str: &str) -> Result<String, JsValue> {
+    let board = match Board::from_str(board_str) {
+        Ok(board) => board,
+        Err(_) => return Err(JsValue::from_str(SolveError::InvalidInput.message())),
+    };
+
+    // See the `catch_unwind` in `solve_sudoku` above for why asserting
+    // unwind-safety on a `Board` carrying `Rc<dyn Constraint>` is sound.
+    match panic::catch_unwind(AssertUnwindSafe(|| solver::solve_checked(&board))) {
+        Ok(Ok(solved)) => Ok(solved.to_string()),
+        Ok(Err(error)) => Err(JsValue::from_str(error.message())),
+        Err(_) => Err(JsValue::from_str(SolveError::SolverPanic.message())),
+    }
+}
+
+/// An interactive play session: a board plus its undo/redo move history.
+/// Exposed as a stateful wasm object (rather than a plain function taking
+/// and returning a board string) since undo/redo need the history to
+/// persist across calls.
+#[wasm_bindgen]
+pub struct GameSession {
+    board: Board,
+    history: History,
+}
+
+#[wasm_bindgen]
+impl GameSession {
+    /// Start a new session from an encoded board string.
+    ///
+    /// ### Errors
+    ///
+    /// * A `JsValue` error if `board_str` is invalid.
+    #[wasm_bindgen(constructor)]
+    pub fn new(board_str: &str) -> Result<GameSession, JsValue> {
+        let board = Board::from_str(board_str).map_err(|e| JsValue::from_str(&e))?;
+        Ok(GameSession {
+            board,
+            history: History::new(),
+        })
+    }
+
+    /// The current board as an encoded string.
+    #[wasm_bindgen(getter)]
+    pub fn board(&self) -> String {
+        self.board.to_string()
+    }
+
+    /// Attempt to place `value` at `index` (`value = 0` clears the cell),
+    /// gated by `Board::is_valid_move`.
+    ///
+    /// ### Returns
+    ///
+    /// A `JsValue` containing the serialized `MoveResult`: the board after
+    /// the attempt, and whether the move conflicted and was left unapplied.
+    pub fn make_move(&mut self, index: usize, value: u8) -> JsValue {
+        let board = &mut self.board;
+        let history = &mut self.history;
+        // See the `catch_unwind` in `solve_sudoku` above for why asserting
+        // unwind-safety on a `Board` carrying `Rc<dyn Constraint>` is sound.
+        let applied =
+            panic::catch_unwind(AssertUnwindSafe(|| history.apply_move(board, index, value)))
+                .unwrap_or(false);
+        self.result(!applied)
+    }
+
+    /// Undo the most recent move.
+    ///
+    /// ### Returns
+    ///
+    /// A `JsValue` containing the serialized `MoveResult`: the board after
+    /// undoing, with `conflict = true` if there was nothing to undo.
+    pub fn undo(&mut self) -> JsValue {
+        let board = &mut self.board;
+        let history = &mut self.history;
+        let undone =
+            panic::catch_unwind(AssertUnwindSafe(|| history.undo(board))).unwrap_or(false);
+        self.result(!undone)
+    }
+
+    /// Redo the most recently undone move.
+    ///
+    /// ### Returns
+    ///
+    /// A `JsValue` containing the serialized `MoveResult`: the board after
+    /// redoing, with `conflict = true` if there was nothing to redo.
+    pub fn redo(&mut self) -> JsValue {
+        let board = &mut self.board;
+        let history = &mut self.history;
+        let redone =
+            panic::catch_unwind(AssertUnwindSafe(|| history.redo(board))).unwrap_or(false);
+        self.result(!redone)
+    }
+}
+
+impl GameSession {
+    fn result(&self, conflict: bool) -> JsValue {
+        let result = MoveResult {
+            board: self.board.to_string(),
+            conflict,
+        };
+        serde_wasm_bindgen::to_value(&result).unwrap()
+    }
+}
@@ -18,75 +18,91 @@
 
 //! A logical Sudoku solver that uses human-like techniques.
 
-use crate::board::Board;
+use crate::board::{Board, MAX_ORDER, MIN_ORDER};
 use crate::types::{CauseCell, Elimination, Placement, SolvingStep};
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
+
+/// Bitmask with all `side` candidate bits set, for the given board `order`.
+fn all_candidates(order: u8) -> u32 {
+    let side = order as u32 * order as u32;
+    if side == 32 {
+        u32::MAX
+    } else {
+        (1u32 << side) - 1
+    }
+}
 
-/// Bitmask representing all candidates (1-9) for a cell.
-const ALL_CANDIDATES: u16 = 0b111111111;
+/// The row/column/box/peer layout for a single board order.
+struct Units {
+    rows: Vec<Vec<usize>>,
+    cols: Vec<Vec<usize>>,
+    boxes: Vec<Vec<usize>>,
+    all: Vec<Vec<usize>>,
+    /// A map from a cell index to the indices of its peers (same row/col/box).
+    peers: Vec<Vec<usize>>,
+}
 
-// Pre-calculate and cache indices for all rows, columns, boxes, and peer cells.
-// This avoids repeated calculations in hot loops within the solver.
-lazy_static::lazy_static! {
-    static ref ROW_UNITS: [[usize; 9]; 9] = {
-        let mut units = [[0; 9]; 9];
-        for (i, row) in units.iter_mut().enumerate() {
-            for (j, cell) in row.iter_mut().enumerate() {
-                *cell = i * 9 + j;
-            }
-        }
-        units
-    };
-    static ref COL_UNITS: [[usize; 9]; 9] = {
-        let mut units = [[0; 9]; 9];
-        for (i, row) in units.iter_mut().enumerate() {
-            for (j, cell) in row.iter_mut().enumerate() {
-                *cell = j * 9 + i;
-            }
-        }
-        units
-    };
-    static ref BOX_UNITS: [[usize; 9]; 9] = {
-        let mut units = [[0; 9]; 9];
-        for (i, unit) in units.iter_mut().enumerate() {
-            let start_row = (i / 3) * 3;
-            let start_col = (i % 3) * 3;
-            for (j, cell) in unit.iter_mut().enumerate() {
-                *cell = (start_row + j / 3) * 9 + (start_col + j % 3);
-            }
+fn build_units(order: u8) -> Units {
+    let order = order as usize;
+    let side = order * order;
+
+    let mut rows = vec![Vec::with_capacity(side); side];
+    let mut cols = vec![Vec::with_capacity(side); side];
+    let mut boxes = vec![Vec::with_capacity(side); side];
+
+    for row in 0..side {
+        for col in 0..side {
+            let index = row * side + col;
+            let box_index = (row / order) * order + (col / order);
+            rows[row].push(index);
+            cols[col].push(index);
+            boxes[box_index].push(index);
         }
-        units
-    };
-    /// A collection of all 27 units (9 rows, 9 columns, 9 boxes).
-    static ref ALL_UNITS: Vec<&'static [usize]> = {
-        let mut units = Vec::with_capacity(27);
-        units.extend(ROW_UNITS.iter().map(|u| &u[..]));
-        units.extend(COL_UNITS.iter().map(|u| &u[..]));
-        units.extend(BOX_UNITS.iter().map(|u| &u[..]));
-        units
-    };
-    /// A map from a cell index to a vector of its 20 peers.
-    static ref PEER_MAP: [Vec<usize>; 81] = {
-        let mut map = [(); 81].map(|_| Vec::with_capacity(20));
-        for (i, peers_vec) in map.iter_mut().enumerate() {
-            let mut peers = HashSet::new();
-            let row = i / 9;
-            let col = i % 9;
-
-            for c in 0..9 { peers.insert(row * 9 + c); }
-            for r in 0..9 { peers.insert(r * 9 + col); }
-            let start_row = (row / 3) * 3;
-            let start_col = (col / 3) * 3;
-            for r_offset in 0..3 {
-                for c_offset in 0..3 {
-                    peers.insert((start_row + r_offset) * 9 + (start_col + c_offset));
-                }
-            }
-            peers.remove(&i);
-            *peers_vec = peers.into_iter().collect();
-        }
-        map
-    };
+    }
+
+    let mut all = Vec::with_capacity(side * 3);
+    all.extend(rows.iter().cloned());
+    all.extend(cols.iter().cloned());
+    all.extend(boxes.iter().cloned());
+
+    let mut peers = vec![Vec::with_capacity(3 * (side - 1)); side * side];
+    for (i, peers_vec) in peers.iter_mut().enumerate() {
+        let row = i / side;
+        let col = i % side;
+        let box_index = (row / order) * order + (col / order);
+
+        let mut set = HashSet::new();
+        set.extend(rows[row].iter().copied());
+        set.extend(cols[col].iter().copied());
+        set.extend(boxes[box_index].iter().copied());
+        set.remove(&i);
+        *peers_vec = set.into_iter().collect();
+        // `HashSet` iteration order is randomized per run; without a stable
+        // order here, the dirty worklist it feeds (via `eliminate_from_peers`)
+        // would process peers in a different sequence every run, making
+        // which naked/hidden single or technique fires first nondeterministic.
+        peers_vec.sort_unstable();
+    }
+
+    Units {
+        rows,
+        cols,
+        boxes,
+        all,
+        peers,
+    }
+}
+
+// Pre-calculate and cache the row/column/box/peer layout for every supported
+// board order. This avoids repeated calculations in hot loops within the
+// solver.
+lazy_static::lazy_static! {
+    static ref UNIT_LAYOUTS: Vec<Units> =
+        (MIN_ORDER..=MAX_ORDER).map(build_units).collect();
+}
+
+fn units_for_order(order: u8) -> &'static Units {
+    &UNIT_LAYOUTS[(order - MIN_ORDER) as usize]
 }
 
 /// Represents the logical difficulty of a solving technique.
@@ -95,50 +111,107 @@ pub enum TechniqueLevel {
     None,         // No logical moves found
     Basic,        // Naked/Hidden Singles
     Intermediate, // Pointing Subsets, Naked/Hidden Pairs/Triples
+    Advanced,     // X-Wing, Y-Wing, Swordfish, WXYZ-Wing
+    Expert,       // Forcing Chains
+}
+
+/// The difficulty weight contributed by a single solving step, used to
+/// compute a puzzle's cumulative numeric difficulty score.
+fn technique_weight(technique: &str) -> u32 {
+    match technique {
+        "NakedSingle" => 1,
+        "HiddenSingle" => 2,
+        "PointingPair" | "PointingTriple" | "NakedPair" | "BoxLineReduction" => 5,
+        "HiddenPair" => 6,
+        "NakedTriple" => 7,
+        "HiddenTriple" => 8,
+        "NakedQuad" => 9,
+        "HiddenQuad" => 10,
+        "XWing" => 10,
+        "YWing" => 12,
+        "Swordfish" => 20,
+        "WXYZWing" => 25,
+        "ForcingChain" => 30,
+        "Probe" => 40,
+        _ => 0,
+    }
+}
+
+/// Compute the cumulative numeric difficulty score of a board: the sum of
+/// each solving step's technique weight across the full solve trace.
+/// Finer-grained than [`TechniqueLevel`], which only reports the single
+/// hardest technique used.
+pub fn difficulty_score(board: &Board) -> u32 {
+    let (steps, _) = solve_with_steps(board);
+    steps.iter().map(|step| technique_weight(&step.technique)).sum()
 }
 
 /// Convert a bitmask of candidates into a `Vec` of numbers.
-fn mask_to_vec(mask: u16) -> Vec<u8> {
-    (1..=9)
-        .filter(|&num| (mask >> (num - 1)) & 1 == 1)
+fn mask_to_vec(mask: u32) -> Vec<u8> {
+    (0..32)
+        .filter(|bit| (mask >> bit) & 1 == 1)
+        .map(|bit| bit as u8 + 1)
         .collect()
 }
 
 /// A Sudoku board with candidate tracking for logical solving.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct LogicalBoard {
+    /// The board order (box side length).
+    pub order: u8,
     /// The definitive numbers on the board (0 for empty).
-    pub cells: [u8; 81],
-    /// A bitmask for each cell representing possible candidates (1-9).
+    pub cells: Vec<u8>,
+    /// A bitmask for each cell representing possible candidates.
     /// A `0` indicates the cell is filled.
-    pub candidates: [u16; 81],
+    pub candidates: Vec<u32>,
+    /// Cells whose candidates changed since the last drain of
+    /// [`Self::propagate`], and so may now resolve to a naked or hidden
+    /// single. Driven as a worklist rather than rescanning the whole board.
+    dirty: VecDeque<usize>,
 }
 
 impl LogicalBoard {
     /// Create a `LogicalBoard` from a simple `Board` by calculating initial candidates.
     pub fn from_board(board: &Board) -> Self {
+        let cell_count = board.cells.len();
+        let full_mask = all_candidates(board.order);
         let mut logical_board = LogicalBoard {
-            cells: board.cells,
-            candidates: [0; 81],
+            order: board.order,
+            cells: board.cells.clone(),
+            candidates: vec![0; cell_count],
+            dirty: VecDeque::new(),
         };
 
         // Initialize candidates for all empty cells.
-        for i in 0..81 {
+        for i in 0..cell_count {
             if logical_board.cells[i] == 0 {
-                logical_board.candidates[i] = ALL_CANDIDATES;
+                logical_board.candidates[i] = full_mask;
             }
         }
 
         // Propagate constraints from existing numbers to establish the initial candidate state.
-        for i in 0..81 {
+        for i in 0..cell_count {
             if logical_board.cells[i] != 0 {
                 logical_board.eliminate_from_peers(i, logical_board.cells[i]);
             }
         }
+
+        // Seed the worklist in index order so the first `propagate()` scans
+        // the board the same way the old full rescan did.
+        logical_board.dirty.clear();
+        logical_board
+            .dirty
+            .extend((0..cell_count).filter(|&i| logical_board.cells[i] == 0));
         logical_board
     }
 
-    /// Place a number on the board and update the candidates of its peers.
+    fn units(&self) -> &'static Units {
+        units_for_order(self.order)
+    }
+
+    /// Place a number on the board and update the candidates of its peers,
+    /// marking every peer whose candidates changed as dirty so a later call
+    /// to [`Self::propagate`] picks up any single it collapsed to.
     fn set_cell(&mut self, index: usize, value: u8) -> bool {
         if self.cells[index] != 0 {
             return false;
@@ -149,86 +222,142 @@ impl LogicalBoard {
         true
     }
 
-    /// Eliminate a candidate from all peer cells of a given index.
+    /// Eliminate a candidate from all peer cells of a given index, pushing
+    /// any peer whose candidate mask actually shrank onto the dirty queue.
     fn eliminate_from_peers(&mut self, index: usize, value: u8) {
-        let elimination_mask = !(1 << (value - 1));
-        for &peer_index in &PEER_MAP[index] {
-            self.candidates[peer_index] &= elimination_mask;
+        let mask = 1u32 << (value - 1);
+        for &peer_index in &self.units().peers[index].clone() {
+            self.eliminate_candidate(peer_index, mask);
         }
     }
 
-    /// Find the first available "Naked Single" on the board.
-    /// A Naked Single is a cell that has only one possible candidate.
-    fn find_naked_single(&self) -> Option<SolvingStep> {
-        for i in 0..81 {
-            if self.cells[i] == 0 && self.candidates[i].count_ones() == 1 {
-                let value = (self.candidates[i].trailing_zeros() + 1) as u8;
-                let eliminations = PEER_MAP[i]
-                    .iter()
-                    .filter(|&&peer_idx| {
-                        self.cells[peer_idx] == 0
-                            && (self.candidates[peer_idx] & (1 << (value - 1))) != 0
-                    })
-                    .map(|&peer_idx| Elimination {
-                        index: peer_idx,
-                        value,
-                    })
-                    .collect();
+    /// Clear `mask`'s bit from `index`'s candidates, marking `index` dirty
+    /// if doing so actually changed anything (and it is still empty).
+    fn eliminate_candidate(&mut self, index: usize, mask: u32) {
+        if self.candidates[index] & mask == 0 {
+            return;
+        }
+        self.candidates[index] &= !mask;
+        if self.cells[index] == 0 {
+            self.dirty.push_back(index);
+        }
+    }
 
-                return Some(SolvingStep {
-                    technique: "NakedSingle".to_string(),
-                    placements: vec![Placement { index: i, value }],
-                    eliminations,
-                    cause: vec![],
-                });
+    /// Pop dirty cells until one resolves to a naked or hidden single,
+    /// returning its index, value and technique name without applying it.
+    /// Cells that are already filled, or have no candidates left (a
+    /// contradiction, left for the caller to detect), are silently skipped.
+    ///
+    /// Naked singles are checked across the *entire* dirty set before any
+    /// hidden single is considered, preserving the cheap-before-expensive
+    /// technique ordering documented on [`propagate_techniques`] even though
+    /// the worklist pops cells in dirty-order rather than a full board
+    /// rescan. Cells that turn out to need a hidden-single check are set
+    /// aside in `deferred` and, if a naked single is found first, pushed
+    /// back onto the queue so they're reconsidered (possibly now naked
+    /// themselves) on the next call.
+    fn next_single(&mut self) -> Option<(usize, u8, &'static str)> {
+        let mut deferred = Vec::new();
+        while let Some(cell) = self.dirty.pop_front() {
+            if self.cells[cell] != 0 || self.candidates[cell] == 0 {
+                continue;
+            }
+            if self.candidates[cell].count_ones() == 1 {
+                self.dirty.extend(deferred);
+                let value = (self.candidates[cell].trailing_zeros() + 1) as u8;
+                return Some((cell, value, "NakedSingle"));
+            }
+            deferred.push(cell);
+        }
+        for (i, &cell) in deferred.iter().enumerate() {
+            if let Some(value) = self.hidden_single_for_cell(cell) {
+                // Requeue every deferred cell we didn't end up using so a
+                // hidden single found early in the scan doesn't drop the
+                // rest of the dirty set on the floor.
+                self.dirty.extend(deferred[..i].iter().copied());
+                self.dirty.extend(deferred[i + 1..].iter().copied());
+                return Some((cell, value, "HiddenSingle"));
             }
         }
         None
     }
 
-    /// Find a "Hidden Single" in a given group of cells (row, column, or box).
-    /// A Hidden Single is a candidate that appears only once within a unit.
-    fn find_hidden_single_in_group(&self, group: &[usize]) -> Option<SolvingStep> {
-        for num in 1..=9 {
-            if let Some(step) = self.try_find_hidden_single_for_number(group, num) {
-                return Some(step);
+    /// Does `cell` hold a digit confined, among its own empty candidates, to
+    /// a single cell of one of its units (row, column or box)? This is the
+    /// cheap, single-cell-scoped form of a Hidden Single used by the dirty
+    /// worklist in [`Self::propagate`]; [`Self::find_hidden_subset`] handles
+    /// the general (and more expensive) whole-unit search.
+    fn hidden_single_for_cell(&self, cell: usize) -> Option<u8> {
+        let side = self.order as usize * self.order as usize;
+        let order = self.order as usize;
+        let row = cell / side;
+        let col = cell % side;
+        let box_index = (row / order) * order + (col / order);
+        let units = self.units();
+
+        for unit in [&units.rows[row], &units.cols[col], &units.boxes[box_index]] {
+            for value in mask_to_vec(self.candidates[cell]) {
+                let mask = 1u32 << (value - 1);
+                let count = unit
+                    .iter()
+                    .filter(|&&i| self.cells[i] == 0 && (self.candidates[i] & mask) != 0)
+                    .count();
+                if count == 1 {
+                    return Some(value);
+                }
             }
         }
         None
     }
 
-    /// Try to find a hidden single for a specific number in a group.
-    fn try_find_hidden_single_for_number(&self, group: &[usize], num: u8) -> Option<SolvingStep> {
-        let mask = 1 << (num - 1);
-        let potential_indices: Vec<usize> = group
-            .iter()
-            .filter(|&&index| self.cells[index] == 0 && (self.candidates[index] & mask) != 0)
-            .cloned()
-            .collect();
-
-        if potential_indices.len() != 1 {
-            return None;
+    /// Drain the dirty-cell worklist to a fixed point, placing every naked or
+    /// hidden single found in a dirty cell. Each placement marks dirty the
+    /// peers whose candidates it shrank, so a single collapse cascades
+    /// immediately instead of waiting for the next full-board rescan. Only
+    /// cells whose own candidates changed are queued: a cell that becomes a
+    /// hidden single because a unit mate lost the digit is not picked up
+    /// here unless it is dirty for another reason. Returns whether any cell was
+    /// placed. Shared by [`solve_with_steps`]'s logical propagation and the
+    /// backtracking fallback, so both see the same cheap deductions.
+    pub(crate) fn propagate(&mut self) -> bool {
+        let mut changed = false;
+        while let Some((cell, value, _)) = self.next_single() {
+            self.set_cell(cell, value);
+            changed = true;
         }
+        changed
+    }
 
-        let index = potential_indices[0];
-        let value = num;
-        let mut eliminations = self.collect_peer_eliminations(index, value);
+    /// Like [`Self::propagate`], but records each placement as a
+    /// `SolvingStep` rather than discarding it. Returns whether a single
+    /// step was applied (not the full fixed point), so callers can interleave
+    /// cheap propagation with the more expensive unit-scanning techniques.
+    fn propagate_step(&mut self, steps: &mut Vec<SolvingStep>) -> bool {
+        let Some((cell, value, technique)) = self.next_single() else {
+            return false;
+        };
 
-        // Also eliminate other candidates from the cell itself.
-        eliminations.extend(self.collect_cell_eliminations(index, value));
+        let mut eliminations = self.collect_peer_eliminations(cell, value);
+        if technique == "HiddenSingle" {
+            eliminations.extend(self.collect_cell_eliminations(cell, value));
+        }
+        self.set_cell(cell, value);
 
-        Some(SolvingStep {
-            technique: "HiddenSingle".to_string(),
-            placements: vec![Placement { index, value }],
+        steps.push(SolvingStep {
+            technique: technique.to_string(),
+            placements: vec![Placement { index: cell, value }],
             eliminations,
             cause: vec![],
-        })
+        });
+        true
     }
 
     /// Collect eliminations from peer cells for a given index and value.
     fn collect_peer_eliminations(&self, index: usize, value: u8) -> Vec<Elimination> {
-        let mask = 1 << (value - 1);
-        PEER_MAP[index]
+        let mask = 1u32 << (value - 1);
+        self.units()
+            .peers
+            [index]
             .iter()
             .filter(|&&p_idx| self.cells[p_idx] == 0 && (self.candidates[p_idx] & mask) != 0)
             .map(|&p_idx| Elimination {
@@ -240,18 +369,19 @@ impl LogicalBoard {
 
     /// Collect eliminations for other candidates in the same cell.
     fn collect_cell_eliminations(&self, index: usize, value: u8) -> Vec<Elimination> {
-        (1..=9)
+        (1..=(self.order as u32 * self.order as u32) as u8)
             .filter(|&cand| cand != value && (self.candidates[index] & (1 << (cand - 1))) != 0)
             .map(|cand| Elimination { index, value: cand })
             .collect()
     }
 
-    /// Find Naked Subsets (Pairs, Triples) in any unit.
-    /// A Naked Pair is two cells in the same unit that have the exact same two candidates.
+    /// Find Naked Subsets (Pairs, Triples, Quads) in any unit: `size` cells
+    /// in the same unit whose candidates, combined, occupy exactly `size`
+    /// values. Those values can then be eliminated from the rest of the unit.
     fn find_naked_subset(&self, size: usize) -> Option<SolvingStep> {
         let tech_name = self.get_technique_name(size);
 
-        for unit in ALL_UNITS.iter() {
+        for unit in self.units().all.iter() {
             if let Some(step) = self.find_naked_subset_in_unit(unit, size, &tech_name) {
                 return Some(step);
             }
@@ -266,12 +396,14 @@ impl LogicalBoard {
             match size {
                 2 => "Pair",
                 3 => "Triple",
+                4 => "Quad",
                 _ => "Subset",
             }
         )
     }
 
-    /// Find a naked subset within a specific unit.
+    /// Find a naked subset within a specific unit, trying every `size`-cell
+    /// combination of the unit's sparsest empty cells.
     fn find_naked_subset_in_unit(
         &self,
         unit: &[usize],
@@ -288,67 +420,43 @@ impl LogicalBoard {
             return None;
         }
 
-        // A simplified combination generator for pairs.
-        if size == 2 {
-            return self.find_naked_pair_in_cells(&empty_cells, unit, tech_name);
-        }
-
-        None
-    }
-
-    /// Find a naked pair within the given empty cells of a unit.
-    fn find_naked_pair_in_cells(
-        &self,
-        empty_cells: &[usize],
-        unit: &[usize],
-        tech_name: &str,
-    ) -> Option<SolvingStep> {
-        for i in 0..empty_cells.len() {
-            for j in (i + 1)..empty_cells.len() {
-                let c1_idx = empty_cells[i];
-                let c2_idx = empty_cells[j];
-
-                if !self.is_valid_naked_pair(c1_idx, c2_idx) {
-                    continue;
-                }
-
-                let combined_mask = self.candidates[c1_idx];
-                let cause_cells = vec![c1_idx, c2_idx];
-                let eliminations =
-                    self.collect_naked_subset_eliminations(unit, &cause_cells, combined_mask);
+        for combo in Self::combinations(&empty_cells, size) {
+            let combined_mask = combo
+                .iter()
+                .fold(0u32, |mask, &idx| mask | self.candidates[idx]);
+            if combined_mask.count_ones() as usize != size {
+                continue;
+            }
 
-                if !eliminations.is_empty() {
-                    let cause_cands = mask_to_vec(combined_mask);
-                    return Some(SolvingStep {
-                        technique: tech_name.to_string(),
-                        placements: vec![],
-                        eliminations,
-                        cause: cause_cells
-                            .iter()
-                            .map(|&idx| CauseCell {
-                                index: idx,
-                                candidates: cause_cands.clone(),
-                            })
-                            .collect(),
-                    });
-                }
+            let eliminations =
+                self.collect_naked_subset_eliminations(unit, &combo, combined_mask);
+            if eliminations.is_empty() {
+                continue;
             }
+
+            let cause_cands = mask_to_vec(combined_mask);
+            return Some(SolvingStep {
+                technique: tech_name.to_string(),
+                placements: vec![],
+                eliminations,
+                cause: combo
+                    .iter()
+                    .map(|&idx| CauseCell {
+                        index: idx,
+                        candidates: cause_cands.clone(),
+                    })
+                    .collect(),
+            });
         }
         None
     }
 
-    /// Check if two cells form a valid naked pair.
-    fn is_valid_naked_pair(&self, c1_idx: usize, c2_idx: usize) -> bool {
-        self.candidates[c1_idx] == self.candidates[c2_idx]
-            && self.candidates[c1_idx].count_ones() == 2
-    }
-
     /// Collect eliminations for a naked subset.
     fn collect_naked_subset_eliminations(
         &self,
         unit: &[usize],
         cause_cells: &[usize],
-        combined_mask: u16,
+        combined_mask: u32,
     ) -> Vec<Elimination> {
         let mut eliminations = Vec::new();
 
@@ -372,11 +480,114 @@ impl LogicalBoard {
         eliminations
     }
 
+    /// Find Hidden Subsets (Pairs, Triples, Quads) in any unit: `size`
+    /// digits confined, between them, to the same `size` cells of a unit.
+    /// All other candidates can then be eliminated from those cells.
+    fn find_hidden_subset(&self, size: usize) -> Option<SolvingStep> {
+        let tech_name = self.get_hidden_technique_name(size);
+
+        for unit in self.units().all.iter() {
+            if let Some(step) = self.find_hidden_subset_in_unit(unit, size, &tech_name) {
+                return Some(step);
+            }
+        }
+        None
+    }
+
+    /// Get the technique name based on hidden subset size.
+    fn get_hidden_technique_name(&self, size: usize) -> String {
+        format!(
+            "Hidden{}",
+            match size {
+                2 => "Pair",
+                3 => "Triple",
+                4 => "Quad",
+                _ => "Subset",
+            }
+        )
+    }
+
+    /// Find a hidden subset within a specific unit, trying every `size`-digit
+    /// combination of digits confined to at most `size` cells of the unit.
+    fn find_hidden_subset_in_unit(
+        &self,
+        unit: &[usize],
+        size: usize,
+        tech_name: &str,
+    ) -> Option<SolvingStep> {
+        let side = self.order as u32 * self.order as u32;
+        let empty_cells: Vec<usize> = unit
+            .iter()
+            .filter(|&&i| self.cells[i] == 0)
+            .cloned()
+            .collect();
+
+        if empty_cells.len() <= size {
+            return None;
+        }
+
+        let digits: Vec<u8> = (1..=side as u8)
+            .filter(|&num| {
+                let mask = 1u32 << (num - 1);
+                let count = empty_cells
+                    .iter()
+                    .filter(|&&i| (self.candidates[i] & mask) != 0)
+                    .count();
+                count >= 1 && count <= size
+            })
+            .collect();
+
+        if digits.len() < size {
+            return None;
+        }
+
+        for combo in Self::combinations(&digits, size) {
+            let combo_mask = combo.iter().fold(0u32, |mask, &d| mask | (1 << (d - 1)));
+            let cells_with_any: Vec<usize> = empty_cells
+                .iter()
+                .filter(|&&i| (self.candidates[i] & combo_mask) != 0)
+                .cloned()
+                .collect();
+
+            if cells_with_any.len() != size {
+                continue;
+            }
+
+            let eliminations: Vec<Elimination> = cells_with_any
+                .iter()
+                .flat_map(|&idx| {
+                    mask_to_vec(self.candidates[idx] & !combo_mask)
+                        .into_iter()
+                        .map(move |value| Elimination { index: idx, value })
+                })
+                .collect();
+
+            if eliminations.is_empty() {
+                continue;
+            }
+
+            return Some(SolvingStep {
+                technique: tech_name.to_string(),
+                placements: vec![],
+                eliminations,
+                cause: cells_with_any
+                    .iter()
+                    .map(|&idx| CauseCell {
+                        index: idx,
+                        candidates: combo.clone(),
+                    })
+                    .collect(),
+            });
+        }
+        None
+    }
+
     /// Find Pointing Pairs/Triples.
     /// This occurs when a candidate within a box is confined to a single row or column.
     fn find_pointing_subset(&self) -> Option<SolvingStep> {
-        for box_unit in BOX_UNITS.iter() {
-            for num in 1..=9 {
+        let side = self.order as u32 * self.order as u32;
+        for box_unit in self.units().boxes.iter() {
+            for num in 1..=side as u8 {
                 if let Some(step) = self.try_find_pointing_subset_in_box(box_unit, num) {
                     return Some(step);
                 }
@@ -387,22 +598,23 @@ impl LogicalBoard {
 
     /// Try to find a pointing subset for a specific number in a box.
     fn try_find_pointing_subset_in_box(&self, box_unit: &[usize], num: u8) -> Option<SolvingStep> {
-        let mask = 1 << (num - 1);
+        let mask = 1u32 << (num - 1);
         let cells_with_cand: Vec<usize> = box_unit
             .iter()
             .filter(|&&i| self.cells[i] == 0 && (self.candidates[i] & mask) != 0)
             .cloned()
             .collect();
 
-        if cells_with_cand.len() < 2 || cells_with_cand.len() > 3 {
+        if cells_with_cand.len() < 2 || cells_with_cand.len() > self.order as usize {
             return None;
         }
 
-        let first_row = cells_with_cand[0] / 9;
-        let first_col = cells_with_cand[0] % 9;
+        let side = self.order as usize * self.order as usize;
+        let first_row = cells_with_cand[0] / side;
+        let first_col = cells_with_cand[0] % side;
 
-        let all_in_same_row = cells_with_cand.iter().all(|&i| i / 9 == first_row);
-        let all_in_same_col = cells_with_cand.iter().all(|&i| i % 9 == first_col);
+        let all_in_same_row = cells_with_cand.iter().all(|&i| i / side == first_row);
+        let all_in_same_col = cells_with_cand.iter().all(|&i| i % side == first_col);
 
         if all_in_same_row {
             return self.create_pointing_subset_step_for_row(
@@ -434,12 +646,13 @@ impl LogicalBoard {
         cells_with_cand: &[usize],
         first_row: usize,
         num: u8,
-        mask: u16,
+        mask: u32,
     ) -> Option<SolvingStep> {
+        let side = self.order as usize * self.order as usize;
         let mut elims = Vec::new();
 
-        for col in 0..9 {
-            let idx = first_row * 9 + col;
+        for col in 0..side {
+            let idx = first_row * side + col;
             if !box_unit.contains(&idx)
                 && self.cells[idx] == 0
                 && (self.candidates[idx] & mask) != 0
@@ -465,12 +678,13 @@ impl LogicalBoard {
         cells_with_cand: &[usize],
         first_col: usize,
         num: u8,
-        mask: u16,
+        mask: u32,
     ) -> Option<SolvingStep> {
+        let side = self.order as usize * self.order as usize;
         let mut elims = Vec::new();
 
-        for row in 0..9 {
-            let idx = row * 9 + first_col;
+        for row in 0..side {
+            let idx = row * side + first_col;
             if !box_unit.contains(&idx)
                 && self.cells[idx] == 0
                 && (self.candidates[idx] & mask) != 0
@@ -515,55 +729,708 @@ impl LogicalBoard {
                 .collect(),
         }
     }
+
+    /// Find Box/Line Reductions ("claiming"), the complement of pointing
+    /// subsets: when a candidate within a row or column is confined to a
+    /// single box, it can be eliminated from the rest of that box.
+    fn find_box_line_reduction(&self) -> Option<SolvingStep> {
+        let side = self.order as u32 * self.order as u32;
+        let units = self.units();
+        for line in units.rows.iter().chain(units.cols.iter()) {
+            for num in 1..=side as u8 {
+                if let Some(step) = self.try_find_box_line_reduction_in_line(line, num) {
+                    return Some(step);
+                }
+            }
+        }
+        None
+    }
+
+    /// Try to find a box/line reduction for a specific number in a row or column.
+    fn try_find_box_line_reduction_in_line(&self, line: &[usize], num: u8) -> Option<SolvingStep> {
+        let mask = 1u32 << (num - 1);
+        let cells_with_cand: Vec<usize> = line
+            .iter()
+            .filter(|&&i| self.cells[i] == 0 && (self.candidates[i] & mask) != 0)
+            .cloned()
+            .collect();
+
+        if cells_with_cand.len() < 2 {
+            return None;
+        }
+
+        let order = self.order as usize;
+        let side = order * order;
+        let box_index_of = |idx: usize| {
+            let row = idx / side;
+            let col = idx % side;
+            (row / order) * order + (col / order)
+        };
+
+        let first_box = box_index_of(cells_with_cand[0]);
+        if !cells_with_cand
+            .iter()
+            .all(|&i| box_index_of(i) == first_box)
+        {
+            return None;
+        }
+
+        let eliminations: Vec<Elimination> = self.units().boxes[first_box]
+            .iter()
+            .filter(|&&i| {
+                !cells_with_cand.contains(&i)
+                    && self.cells[i] == 0
+                    && (self.candidates[i] & mask) != 0
+            })
+            .map(|&i| Elimination { index: i, value: num })
+            .collect();
+
+        if eliminations.is_empty() {
+            return None;
+        }
+
+        Some(SolvingStep {
+            technique: "BoxLineReduction".to_string(),
+            placements: vec![],
+            eliminations,
+            cause: cells_with_cand
+                .iter()
+                .map(|&idx| CauseCell {
+                    index: idx,
+                    candidates: vec![num],
+                })
+                .collect(),
+        })
+    }
+
+    /// Generate all `size`-element combinations of `items` (order-independent).
+    fn combinations<T: Clone>(items: &[T], size: usize) -> Vec<Vec<T>> {
+        if size == 0 {
+            return vec![vec![]];
+        }
+        if items.len() < size {
+            return vec![];
+        }
+        let mut result = Vec::new();
+        for i in 0..=items.len() - size {
+            for mut rest in Self::combinations(&items[i + 1..], size - 1) {
+                rest.insert(0, items[i].clone());
+                result.push(rest);
+            }
+        }
+        result
+    }
+
+    /// Find a basic fish pattern (X-Wing for `size == 2`, Swordfish for
+    /// `size == 3`): a digit confined, across `size` rows, to the same
+    /// `size` columns (or vice versa), eliminating it from the rest of
+    /// those columns/rows.
+    fn find_fish(&self, size: usize, tech_name: &str) -> Option<SolvingStep> {
+        let side = self.order as usize * self.order as usize;
+
+        for num in 1..=side as u8 {
+            let mask = 1u32 << (num - 1);
+            if let Some(step) = self.find_fish_for_number(size, side, num, mask, tech_name, true) {
+                return Some(step);
+            }
+            if let Some(step) = self.find_fish_for_number(size, side, num, mask, tech_name, false)
+            {
+                return Some(step);
+            }
+        }
+        None
+    }
+
+    /// Search for a fish pattern for a single digit, in one orientation.
+    /// `by_row == true` scans rows for confined columns; `false` is the
+    /// transposed (column-confined-to-rows) search.
+    #[allow(clippy::too_many_arguments)]
+    fn find_fish_for_number(
+        &self,
+        size: usize,
+        side: usize,
+        num: u8,
+        mask: u32,
+        tech_name: &str,
+        by_row: bool,
+    ) -> Option<SolvingStep> {
+        let mut lines_with_positions: Vec<(usize, Vec<usize>)> = Vec::new();
+
+        for line in 0..side {
+            let positions: Vec<usize> = (0..side)
+                .filter(|&cross| {
+                    let idx = if by_row { line * side + cross } else { cross * side + line };
+                    self.cells[idx] == 0 && (self.candidates[idx] & mask) != 0
+                })
+                .collect();
+            if positions.len() >= 2 && positions.len() <= size {
+                lines_with_positions.push((line, positions));
+            }
+        }
+
+        for combo in Self::combinations(&lines_with_positions, size) {
+            let mut cross_union: Vec<usize> = Vec::new();
+            for (_, positions) in &combo {
+                for &p in positions {
+                    if !cross_union.contains(&p) {
+                        cross_union.push(p);
+                    }
+                }
+            }
+            if cross_union.len() != size {
+                continue;
+            }
+
+            let lines_in_combo: Vec<usize> = combo.iter().map(|(line, _)| *line).collect();
+            let mut eliminations = Vec::new();
+            for &cross in &cross_union {
+                for line in 0..side {
+                    if lines_in_combo.contains(&line) {
+                        continue;
+                    }
+                    let idx = if by_row { line * side + cross } else { cross * side + line };
+                    if self.cells[idx] == 0 && (self.candidates[idx] & mask) != 0 {
+                        eliminations.push(Elimination { index: idx, value: num });
+                    }
+                }
+            }
+
+            if eliminations.is_empty() {
+                continue;
+            }
+
+            let cause = combo
+                .iter()
+                .flat_map(|(line, positions)| {
+                    positions.iter().map(move |&cross| {
+                        let idx = if by_row { *line * side + cross } else { cross * side + *line };
+                        CauseCell { index: idx, candidates: vec![num] }
+                    })
+                })
+                .collect();
+
+            return Some(SolvingStep {
+                technique: tech_name.to_string(),
+                placements: vec![],
+                eliminations,
+                cause,
+            });
+        }
+        None
+    }
+
+    /// Find an X-Wing: a digit confined, across two rows, to the same two
+    /// columns (or the symmetric column/row case), eliminating it from the
+    /// rest of those columns/rows. The two-line case of [`Self::find_fish`].
+    fn find_x_wing(&self) -> Option<SolvingStep> {
+        self.find_fish(2, "XWing")
+    }
+
+    /// Find a Y-Wing (XY-Wing): a bivalue pivot cell `{x, y}` with two
+    /// bivalue peers `{x, z}` and `{y, z}`. Any cell that is a peer of both
+    /// wings can have `z` eliminated.
+    fn find_y_wing(&self) -> Option<SolvingStep> {
+        let bivalue_cells: Vec<usize> = (0..self.cells.len())
+            .filter(|&i| self.cells[i] == 0 && self.candidates[i].count_ones() == 2)
+            .collect();
+
+        for &pivot in &bivalue_cells {
+            let pivot_mask = self.candidates[pivot];
+            let peers = self.units().peers[pivot].clone();
+
+            for &w1 in &peers {
+                if self.cells[w1] != 0 || self.candidates[w1].count_ones() != 2 {
+                    continue;
+                }
+                let shared1 = self.candidates[w1] & pivot_mask;
+                if shared1.count_ones() != 1 {
+                    continue;
+                }
+                let z_mask = self.candidates[w1] & !pivot_mask;
+                if z_mask.count_ones() != 1 {
+                    continue;
+                }
+
+                for &w2 in &peers {
+                    if w2 == w1 || self.cells[w2] != 0 || self.candidates[w2].count_ones() != 2 {
+                        continue;
+                    }
+                    let shared2 = self.candidates[w2] & pivot_mask;
+                    if shared2.count_ones() != 1 || shared2 == shared1 {
+                        continue;
+                    }
+                    if (self.candidates[w2] & !pivot_mask) != z_mask {
+                        continue;
+                    }
+
+                    let peers_w1 = &self.units().peers[w1];
+                    let peers_w2 = &self.units().peers[w2];
+                    let eliminations: Vec<Elimination> = peers_w1
+                        .iter()
+                        .filter(|c| peers_w2.contains(c))
+                        .filter(|&&c| {
+                            c != pivot
+                                && self.cells[c] == 0
+                                && (self.candidates[c] & z_mask) != 0
+                        })
+                        .map(|&c| Elimination {
+                            index: c,
+                            value: mask_to_vec(z_mask)[0],
+                        })
+                        .collect();
+
+                    if eliminations.is_empty() {
+                        continue;
+                    }
+
+                    let cause = [pivot, w1, w2]
+                        .iter()
+                        .map(|&idx| CauseCell {
+                            index: idx,
+                            candidates: mask_to_vec(self.candidates[idx]),
+                        })
+                        .collect();
+
+                    return Some(SolvingStep {
+                        technique: "YWing".to_string(),
+                        placements: vec![],
+                        eliminations,
+                        cause,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    /// Find a WXYZ-Wing: exactly four cells, each with 2 to 4 candidates,
+    /// whose combined candidate mask has exactly four digits {W, X, Y, Z}.
+    /// A digit is "restricted" if every group cell holding it mutually sees
+    /// every other group cell holding it. When exactly one digit `Z` is
+    /// non-restricted, it can be eliminated from any cell outside the group
+    /// that is a peer of every group cell holding `Z`.
+    ///
+    /// The pigeonhole argument behind this elimination only holds for
+    /// groups of exactly four cells: with fewer cells, the `z` candidate
+    /// need not be forced out of every non-`z` assignment, so the
+    /// elimination would be unsound.
+    fn find_wxyz_wing(&self) -> Option<SolvingStep> {
+        let candidate_cells: Vec<usize> = (0..self.cells.len())
+            .filter(|&i| self.cells[i] == 0 && (2..=4).contains(&self.candidates[i].count_ones()))
+            .collect();
+
+        for combo in Self::combinations(&candidate_cells, 4) {
+            if let Some(step) = self.try_wxyz_wing_group(&combo) {
+                return Some(step);
+            }
+        }
+        None
+    }
+
+    /// Try to build a WXYZ-Wing from a specific group of four cells.
+    fn try_wxyz_wing_group(&self, group: &[usize]) -> Option<SolvingStep> {
+        debug_assert_eq!(group.len(), 4);
+        let combined_mask = group
+            .iter()
+            .fold(0u32, |mask, &idx| mask | self.candidates[idx]);
+        if combined_mask.count_ones() != 4 {
+            return None;
+        }
+
+        let peers = &self.units().peers;
+        let cells_for_digit = |d: u8| -> Vec<usize> {
+            let mask = 1u32 << (d - 1);
+            group
+                .iter()
+                .filter(|&&i| (self.candidates[i] & mask) != 0)
+                .cloned()
+                .collect()
+        };
+
+        let digits = mask_to_vec(combined_mask);
+        let mut non_restricted: Vec<u8> = Vec::new();
+        for &d in &digits {
+            let cells = cells_for_digit(d);
+            let restricted = cells
+                .iter()
+                .all(|&a| cells.iter().all(|&b| a == b || peers[a].contains(&b)));
+            if !restricted {
+                non_restricted.push(d);
+            }
+        }
+
+        if non_restricted.len() != 1 {
+            return None;
+        }
+        let z = non_restricted[0];
+        let z_cells = cells_for_digit(z);
+        let z_mask = 1u32 << (z - 1);
+
+        let eliminations: Vec<Elimination> = (0..self.cells.len())
+            .filter(|&i| {
+                self.cells[i] == 0
+                    && !group.contains(&i)
+                    && (self.candidates[i] & z_mask) != 0
+                    && z_cells.iter().all(|&zc| peers[zc].contains(&i))
+            })
+            .map(|i| Elimination { index: i, value: z })
+            .collect();
+
+        if eliminations.is_empty() {
+            return None;
+        }
+
+        Some(SolvingStep {
+            technique: "WXYZWing".to_string(),
+            placements: vec![],
+            eliminations,
+            cause: group
+                .iter()
+                .map(|&idx| CauseCell {
+                    index: idx,
+                    candidates: mask_to_vec(self.candidates[idx]),
+                })
+                .collect(),
+        })
+    }
+
+    /// Apply naked/hidden singles to a fixed point via [`Self::propagate`].
+    /// Returns `false` if a contradiction is found (an empty cell with no
+    /// remaining candidates).
+    fn propagate_singles(&mut self) -> bool {
+        self.propagate();
+        !self
+            .cells
+            .iter()
+            .enumerate()
+            .any(|(i, &c)| c == 0 && self.candidates[i] == 0)
+    }
+
+    /// Find a "Forcing Chain" elimination: pick a bivalue cell, tentatively
+    /// assign each of its two candidates in turn, and propagate singles from
+    /// each branch. Any candidate eliminated in *both* branches can be
+    /// eliminated for real, since one of the two branches must hold.
+    fn find_forcing_chain(&self) -> Option<SolvingStep> {
+        let side = self.order as usize * self.order as usize;
+        let bivalue_cells: Vec<usize> = (0..self.cells.len())
+            .filter(|&i| self.cells[i] == 0 && self.candidates[i].count_ones() == 2)
+            .collect();
+
+        for &cell in &bivalue_cells {
+            let values = mask_to_vec(self.candidates[cell]);
+            if values.len() != 2 {
+                continue;
+            }
+
+            let mut branches = Vec::with_capacity(2);
+            let mut any_contradiction = false;
+            for &value in &values {
+                let mut branch = self.clone();
+                branch.set_cell(cell, value);
+                if !branch.propagate_singles() {
+                    any_contradiction = true;
+                }
+                branches.push(branch);
+            }
+            // A contradiction in one branch is a simple single-candidate
+            // deduction elsewhere, not a genuine forcing chain; skip it here.
+            if any_contradiction {
+                continue;
+            }
+
+            let mut eliminations = Vec::new();
+            for idx in 0..self.cells.len() {
+                if idx == cell || self.cells[idx] != 0 {
+                    continue;
+                }
+                for value in 1..=side as u8 {
+                    let mask = 1u32 << (value - 1);
+                    if (self.candidates[idx] & mask) == 0 {
+                        continue;
+                    }
+                    // A branch that filled `idx` with exactly `value` confirms
+                    // it rather than eliminating it; treat that as surviving
+                    // too, or a branch's own propagation would make its own
+                    // solved cell look like a contradiction-driven removal.
+                    let survives = |branch: &LogicalBoard| {
+                        branch.cells[idx] == value
+                            || (branch.cells[idx] == 0 && (branch.candidates[idx] & mask) != 0)
+                    };
+                    if !survives(&branches[0]) && !survives(&branches[1]) {
+                        eliminations.push(Elimination { index: idx, value });
+                    }
+                }
+            }
+
+            if eliminations.is_empty() {
+                continue;
+            }
+
+            return Some(SolvingStep {
+                technique: "ForcingChain".to_string(),
+                placements: vec![],
+                eliminations,
+                cause: vec![CauseCell {
+                    index: cell,
+                    candidates: values,
+                }],
+            });
+        }
+        None
+    }
+
+    /// Bounded guess-and-propagate ("Probe"): pick the empty cell with the
+    /// fewest candidates, tentatively assign one, and propagate singles. If
+    /// that leads to a contradiction, the candidate is confirmed impossible
+    /// and eliminated for real. `visited` caches boards already examined (by
+    /// their cell contents) so equivalent branches are not re-explored.
+    fn find_probe(&self, visited: &mut HashSet<Vec<u8>>) -> Option<SolvingStep> {
+        let cell = (0..self.cells.len())
+            .filter(|&i| self.cells[i] == 0)
+            .min_by_key(|&i| self.candidates[i].count_ones())?;
+
+        for value in mask_to_vec(self.candidates[cell]) {
+            let mut branch = self.clone();
+            branch.set_cell(cell, value);
+
+            if !visited.insert(branch.cells.clone()) {
+                continue; // Already examined this exact position.
+            }
+
+            if !branch.propagate_singles() {
+                return Some(SolvingStep {
+                    technique: "Probe".to_string(),
+                    placements: vec![],
+                    eliminations: vec![Elimination { index: cell, value }],
+                    cause: vec![CauseCell {
+                        index: cell,
+                        candidates: mask_to_vec(self.candidates[cell]),
+                    }],
+                });
+            }
+        }
+        None
+    }
 }
 
 /// Solve the board by repeatedly applying logical techniques and return the steps.
 pub fn solve_with_steps(initial_board: &Board) -> (Vec<SolvingStep>, Board) {
     let mut board = LogicalBoard::from_board(initial_board);
     let mut steps = Vec::new();
+    propagate_techniques(&mut board, &mut steps);
+    (steps, Board::from_cells_unchecked(board.order, board.cells))
+}
+
+/// Like [`solve_with_steps`], but when logical techniques stall on a board
+/// that still has empty cells, falls back to depth-first backtracking:
+/// the empty cell with the fewest candidates (MRV) is guessed, the guess is
+/// recorded as a `"Guess"` step, and logical techniques resume on the
+/// resulting board. This lets the UI replay the *entire* solve path,
+/// including any guesses, for puzzles that logic alone cannot finish.
+pub fn solve_with_steps_and_guesses(initial_board: &Board) -> (Vec<SolvingStep>, Board) {
+    let mut board = LogicalBoard::from_board(initial_board);
+    let mut steps = Vec::new();
+    propagate_techniques(&mut board, &mut steps);
+
+    if board.cells.contains(&0) {
+        if let Some((guess_steps, solved_board)) = guess_and_continue(&board) {
+            steps.extend(guess_steps);
+            board = solved_board;
+        }
+    }
+
+    (steps, Board::from_cells_unchecked(board.order, board.cells))
+}
+
+/// Apply every logical technique to a fixed point, recording each applied
+/// step. Shared by [`solve_with_steps`] and the guessing fallback so both
+/// run identical propagation.
+///
+/// Each outer iteration first drains the cheap dirty-cell worklist (naked
+/// and hidden singles, via [`LogicalBoard::propagate_step`]) to its own
+/// fixed point, and only once that worklist is empty does it fall back to
+/// the expensive unit-scanning techniques (subsets, pointing, fish, ...).
+/// Those techniques mark cells dirty through the same
+/// [`LogicalBoard::eliminate_candidate`] path, so a single in a cell they
+/// removed a candidate from is picked up on the next iteration instead of
+/// waiting for a full-board rescan. A hidden single in a cell whose own
+/// candidates they left unchanged is not queued.
+fn propagate_techniques(board: &mut LogicalBoard, steps: &mut Vec<SolvingStep>) {
+    let mut visited_probes = HashSet::new();
 
     loop {
-        let progress = try_naked_single(&mut board, &mut steps)
-            || try_hidden_single(&mut board, &mut steps)
-            || try_naked_pair(&mut board, &mut steps)
-            || try_pointing_subset(&mut board, &mut steps);
+        while board.propagate_step(steps) {}
+
+        let progress = try_naked_subset(board, steps, 2)
+            || try_hidden_subset(board, steps, 2)
+            || try_pointing_subset(board, steps)
+            || try_box_line_reduction(board, steps)
+            || try_naked_subset(board, steps, 3)
+            || try_hidden_subset(board, steps, 3)
+            || try_naked_subset(board, steps, 4)
+            || try_hidden_subset(board, steps, 4)
+            || try_x_wing(board, steps)
+            || try_y_wing(board, steps)
+            || try_fish(board, steps, 3, "Swordfish")
+            || try_wxyz_wing(board, steps)
+            || try_forcing_chain(board, steps)
+            || try_probe(board, steps, &mut visited_probes);
 
         if !progress {
             break;
         }
     }
+}
+
+/// Returns `true` if `board` has an empty cell with no remaining candidates,
+/// i.e. the current assignment cannot be extended to a solution.
+fn has_contradiction(board: &LogicalBoard) -> bool {
+    board
+        .cells
+        .iter()
+        .enumerate()
+        .any(|(i, &c)| c == 0 && board.candidates[i] == 0)
+}
+
+/// The empty cell with the fewest candidates (MRV heuristic), or `None` if
+/// the board has no empty cells left.
+fn most_constrained_cell(board: &LogicalBoard) -> Option<usize> {
+    (0..board.cells.len())
+        .filter(|&i| board.cells[i] == 0)
+        .min_by_key(|&i| board.candidates[i].count_ones())
+}
+
+/// Depth-first backtracking fallback for [`solve_with_steps_and_guesses`]:
+/// guess the most constrained cell, propagate, and recurse. Returns the
+/// guess/logical steps and the completed board for the first candidate that
+/// leads to a solution, or `None` if every candidate leads to a
+/// contradiction.
+fn guess_and_continue(board: &LogicalBoard) -> Option<(Vec<SolvingStep>, LogicalBoard)> {
+    let cell = most_constrained_cell(board)?;
+
+    for value in mask_to_vec(board.candidates[cell]) {
+        let mut branch = board.clone();
+        branch.set_cell(cell, value);
+        if has_contradiction(&branch) {
+            continue;
+        }
+
+        let mut branch_steps = vec![SolvingStep {
+            technique: "Guess".to_string(),
+            placements: vec![Placement { index: cell, value }],
+            eliminations: vec![],
+            cause: vec![],
+        }];
+        propagate_techniques(&mut branch, &mut branch_steps);
+
+        if has_contradiction(&branch) {
+            continue;
+        }
+        if !branch.cells.contains(&0) {
+            return Some((branch_steps, branch));
+        }
+        if let Some((rest_steps, solved)) = guess_and_continue(&branch) {
+            branch_steps.extend(rest_steps);
+            return Some((branch_steps, solved));
+        }
+    }
+    None
+}
+
+/// Count up to `limit` distinct solutions of `board` using candidate-pruned
+/// backtracking (MRV cell selection, plus [`LogicalBoard::propagate`] after
+/// every guess). Pass `2` to test a puzzle for a unique solution without
+/// counting further.
+pub fn count_solutions(board: &Board, limit: usize) -> usize {
+    let logical_board = LogicalBoard::from_board(board);
+    let mut count = 0;
+    count_solutions_recursive(&logical_board, limit, &mut count);
+    count
+}
+
+fn count_solutions_recursive(board: &LogicalBoard, limit: usize, count: &mut usize) {
+    if *count >= limit || has_contradiction(board) {
+        return;
+    }
+
+    let Some(cell) = most_constrained_cell(board) else {
+        *count += 1;
+        return;
+    };
+
+    for value in mask_to_vec(board.candidates[cell]) {
+        if *count >= limit {
+            return;
+        }
+        let mut branch = board.clone();
+        branch.set_cell(cell, value);
+        branch.propagate();
+        count_solutions_recursive(&branch, limit, count);
+    }
+}
+
+/// Fully solve `board` with candidate-pruned backtracking, returning `None`
+/// if no completion exists. Unlike [`count_solutions`], this stops at the
+/// first solution found rather than checking for uniqueness.
+pub fn solve_complete(board: &Board) -> Option<Board> {
+    let logical_board = LogicalBoard::from_board(board);
+    solve_complete_recursive(logical_board)
+        .map(|board| Board::from_cells_unchecked(board.order, board.cells))
+}
+
+fn solve_complete_recursive(board: LogicalBoard) -> Option<LogicalBoard> {
+    if has_contradiction(&board) {
+        return None;
+    }
+
+    let Some(cell) = most_constrained_cell(&board) else {
+        return Some(board);
+    };
+
+    for value in mask_to_vec(board.candidates[cell]) {
+        let mut branch = board.clone();
+        branch.set_cell(cell, value);
+        branch.propagate();
+        if let Some(solved) = solve_complete_recursive(branch) {
+            return Some(solved);
+        }
+    }
+    None
+}
 
-    (steps, Board { cells: board.cells })
+/// Apply a solving step's eliminations to `board`, marking every affected
+/// cell dirty so [`LogicalBoard::propagate_step`] picks up any resulting
+/// single on the next pass.
+fn apply_eliminations(board: &mut LogicalBoard, step: &SolvingStep) {
+    for elim in &step.eliminations {
+        board.eliminate_candidate(elim.index, 1 << (elim.value - 1));
+    }
 }
 
-/// Try to apply a naked single technique.
-fn try_naked_single(board: &mut LogicalBoard, steps: &mut Vec<SolvingStep>) -> bool {
-    if let Some(step) = board.find_naked_single() {
-        board.set_cell(step.placements[0].index, step.placements[0].value);
+/// Try to apply a box/line reduction ("claiming") technique.
+fn try_box_line_reduction(board: &mut LogicalBoard, steps: &mut Vec<SolvingStep>) -> bool {
+    if let Some(step) = board.find_box_line_reduction() {
+        apply_eliminations(board, &step);
         steps.push(step);
         return true;
     }
     false
 }
 
-/// Try to apply a hidden single technique across all units.
-fn try_hidden_single(board: &mut LogicalBoard, steps: &mut Vec<SolvingStep>) -> bool {
-    for unit in ALL_UNITS.iter() {
-        if let Some(step) = board.find_hidden_single_in_group(unit) {
-            board.set_cell(step.placements[0].index, step.placements[0].value);
-            steps.push(step);
-            return true;
-        }
+/// Try to apply a naked subset technique (pair/triple/quad, by `size`).
+fn try_naked_subset(board: &mut LogicalBoard, steps: &mut Vec<SolvingStep>, size: usize) -> bool {
+    if let Some(step) = board.find_naked_subset(size) {
+        apply_eliminations(board, &step);
+        steps.push(step);
+        return true;
     }
     false
 }
 
-/// Try to apply a naked pair technique.
-fn try_naked_pair(board: &mut LogicalBoard, steps: &mut Vec<SolvingStep>) -> bool {
-    if let Some(step) = board.find_naked_subset(2) {
-        for elim in &step.eliminations {
-            board.candidates[elim.index] &= !(1 << (elim.value - 1));
-        }
+/// Try to apply a hidden subset technique (pair/triple/quad, by `size`).
+fn try_hidden_subset(board: &mut LogicalBoard, steps: &mut Vec<SolvingStep>, size: usize) -> bool {
+    if let Some(step) = board.find_hidden_subset(size) {
+        apply_eliminations(board, &step);
         steps.push(step);
         return true;
     }
@@ -573,9 +1440,78 @@ fn try_naked_pair(board: &mut LogicalBoard, steps: &mut Vec<SolvingStep>) -> boo
 /// Try to apply a pointing subset technique.
 fn try_pointing_subset(board: &mut LogicalBoard, steps: &mut Vec<SolvingStep>) -> bool {
     if let Some(step) = board.find_pointing_subset() {
-        for elim in &step.eliminations {
-            board.candidates[elim.index] &= !(1 << (elim.value - 1));
-        }
+        apply_eliminations(board, &step);
+        steps.push(step);
+        return true;
+    }
+    false
+}
+
+/// Try to apply an X-Wing technique.
+fn try_x_wing(board: &mut LogicalBoard, steps: &mut Vec<SolvingStep>) -> bool {
+    if let Some(step) = board.find_x_wing() {
+        apply_eliminations(board, &step);
+        steps.push(step);
+        return true;
+    }
+    false
+}
+
+/// Try to apply a fish technique (`size == 3` is Swordfish; `size == 2`
+/// is equivalent to [`try_x_wing`], which calls the named `find_x_wing`
+/// entry point instead).
+fn try_fish(
+    board: &mut LogicalBoard,
+    steps: &mut Vec<SolvingStep>,
+    size: usize,
+    tech_name: &str,
+) -> bool {
+    if let Some(step) = board.find_fish(size, tech_name) {
+        apply_eliminations(board, &step);
+        steps.push(step);
+        return true;
+    }
+    false
+}
+
+/// Try to apply a Y-Wing (XY-Wing) technique.
+fn try_y_wing(board: &mut LogicalBoard, steps: &mut Vec<SolvingStep>) -> bool {
+    if let Some(step) = board.find_y_wing() {
+        apply_eliminations(board, &step);
+        steps.push(step);
+        return true;
+    }
+    false
+}
+
+/// Try to apply a WXYZ-Wing technique.
+fn try_wxyz_wing(board: &mut LogicalBoard, steps: &mut Vec<SolvingStep>) -> bool {
+    if let Some(step) = board.find_wxyz_wing() {
+        apply_eliminations(board, &step);
+        steps.push(step);
+        return true;
+    }
+    false
+}
+
+/// Try to apply a forcing-chain technique.
+fn try_forcing_chain(board: &mut LogicalBoard, steps: &mut Vec<SolvingStep>) -> bool {
+    if let Some(step) = board.find_forcing_chain() {
+        apply_eliminations(board, &step);
+        steps.push(step);
+        return true;
+    }
+    false
+}
+
+/// Try to apply a "Probe" (bounded guess-and-propagate) technique.
+fn try_probe(
+    board: &mut LogicalBoard,
+    steps: &mut Vec<SolvingStep>,
+    visited: &mut HashSet<Vec<u8>>,
+) -> bool {
+    if let Some(step) = board.find_probe(visited) {
+        apply_eliminations(board, &step);
         steps.push(step);
         return true;
     }
@@ -591,7 +1527,12 @@ pub fn get_difficulty(initial_board: &Board) -> (TechniqueLevel, Board) {
         .iter()
         .map(|step| match step.technique.as_str() {
             "NakedSingle" | "HiddenSingle" => TechniqueLevel::Basic,
-            "PointingPair" | "PointingTriple" | "NakedPair" => TechniqueLevel::Intermediate,
+            "PointingPair" | "PointingTriple" | "BoxLineReduction" | "NakedPair" | "HiddenPair"
+            | "NakedTriple" | "HiddenTriple" => TechniqueLevel::Intermediate,
+            "XWing" | "YWing" | "Swordfish" | "NakedQuad" | "HiddenQuad" | "WXYZWing" => {
+                TechniqueLevel::Advanced
+            }
+            "ForcingChain" | "Probe" => TechniqueLevel::Expert,
             _ => TechniqueLevel::None,
         })
         .max()
@@ -0,0 +1,178 @@
+/*
+* Copyright (C) 2025  Henrique Almeida
+* This file is part of WASudoku.
+
+* WASudoku is free software: you can redistribute it and/or modify
+* it under the terms of the GNU Affero General Public License as published
+* by the Free Software Foundation, either version 3 of the License, or
+* (at your option) any later version.
+
+* WASudoku is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+* GNU Affero General Public License for more details.
+
+* You should have received a copy of the GNU Affero General Public License
+* along with WASudoku.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! A "commit, then selectively open" proof scheme, in the spirit of the
+//! classic pay-to-sudoku protocol: a prover commits to a completed [`Board`]
+//! without revealing it, and a verifier can later challenge individual
+//! rows, columns or boxes to confirm the commitment is consistent with a
+//! real solution of a given puzzle, without ever seeing the full board.
+//!
+//! Each cell gets its own salted commitment `H(value || salt)`, so opening
+//! one unit reveals nothing about the digits anywhere else on the board.
+
+use crate::board::Board;
+use rand::{rng, Rng};
+use sha2::{Digest, Sha256};
+
+/// A single cell's commitment hash.
+pub type CellHash = [u8; 32];
+
+/// A prover-held commitment to a completed board: a salted hash per cell
+/// plus a root hash over all of them. Only [`Commitment::root`] is meant to
+/// be published up front; the per-cell values and salts stay with the
+/// prover until revealed through [`open_unit`].
+pub struct Commitment {
+    order: u8,
+    values: Vec<u8>,
+    salts: Vec<CellHash>,
+    hashes: Vec<CellHash>,
+    /// Hash of all per-cell commitments, suitable for publishing before any
+    /// unit is opened.
+    pub root: CellHash,
+}
+
+/// A revealed row, column or box: the values and salts needed to check
+/// every cell's commitment, in the same order as [`unit_cells`]. Unlike
+/// [`Commitment`], every field here is meant to be shared with the
+/// verifier once the prover agrees to open this particular unit.
+pub struct UnitOpening {
+    pub unit_id: usize,
+    pub values: Vec<u8>,
+    pub salts: Vec<CellHash>,
+}
+
+/// Hash a single cell's value together with its salt.
+fn hash_cell(value: u8, salt: &CellHash) -> CellHash {
+    let mut hasher = Sha256::new();
+    hasher.update([value]);
+    hasher.update(salt);
+    hasher.finalize().into()
+}
+
+/// Hash the full list of per-cell commitments into a single root.
+fn hash_root(hashes: &[CellHash]) -> CellHash {
+    let mut hasher = Sha256::new();
+    for hash in hashes {
+        hasher.update(hash);
+    }
+    hasher.finalize().into()
+}
+
+/// Builds the cell indices of every row, column and box, in a fixed order:
+/// all rows, then all columns, then all boxes. A unit's position in this
+/// list is its `unit_id`.
+fn unit_cells(order: u8) -> Vec<Vec<usize>> {
+    let side = order as usize * order as usize;
+    let mut units = Vec::with_capacity(side * 3);
+
+    for row in 0..side {
+        units.push((0..side).map(|col| row * side + col).collect());
+    }
+    for col in 0..side {
+        units.push((0..side).map(|row| row * side + col).collect());
+    }
+    for box_row in 0..order as usize {
+        for box_col in 0..order as usize {
+            let mut cells = Vec::with_capacity(side);
+            for r in 0..order as usize {
+                for c in 0..order as usize {
+                    let row = box_row * order as usize + r;
+                    let col = box_col * order as usize + c;
+                    cells.push(row * side + col);
+                }
+            }
+            units.push(cells);
+        }
+    }
+    units
+}
+
+/// Commits to a completed `board`, producing a per-cell salted hash and a
+/// root hash over all of them. `board` must be fully filled in; the caller
+/// is responsible for checking that beforehand (e.g. via [`solve`](crate::solver::solve)).
+pub fn commit(board: &Board) -> Commitment {
+    let mut rng = rng();
+    let mut salts = Vec::with_capacity(board.cells.len());
+    let mut hashes = Vec::with_capacity(board.cells.len());
+
+    for &value in &board.cells {
+        let mut salt = [0u8; 32];
+        rng.fill(&mut salt);
+        hashes.push(hash_cell(value, &salt));
+        salts.push(salt);
+    }
+    let root = hash_root(&hashes);
+
+    Commitment {
+        order: board.order,
+        values: board.cells.clone(),
+        salts,
+        hashes,
+        root,
+    }
+}
+
+/// Reveals the values and salts for a single row, column or box, identified
+/// by its position in [`unit_cells`]. Panics if `unit_id` is out of range.
+pub fn open_unit(commitment: &Commitment, unit_id: usize) -> UnitOpening {
+    let cells = &unit_cells(commitment.order)[unit_id];
+    let values = cells.iter().map(|&i| commitment.values[i]).collect();
+    let salts = cells.iter().map(|&i| commitment.salts[i]).collect();
+
+    UnitOpening {
+        unit_id,
+        values,
+        salts,
+    }
+}
+
+/// Checks that `opening` is a valid, honest opening of `commitment` for the
+/// given `puzzle`: every revealed cell's commitment must match its hash,
+/// every clue in the unit must match the puzzle's given, and the revealed
+/// values must form a permutation of `1..=side`.
+pub fn verify_unit(commitment: &Commitment, puzzle: &Board, opening: &UnitOpening) -> bool {
+    if puzzle.order != commitment.order {
+        return false;
+    }
+    let units = unit_cells(commitment.order);
+    let Some(cells) = units.get(opening.unit_id) else {
+        return false;
+    };
+    if cells.len() != opening.values.len() || cells.len() != opening.salts.len() {
+        return false;
+    }
+
+    for (position, &cell) in cells.iter().enumerate() {
+        let value = opening.values[position];
+        let salt = opening.salts[position];
+
+        if hash_cell(value, &salt) != commitment.hashes[cell] {
+            return false;
+        }
+
+        let given = puzzle.cells[cell];
+        if given != 0 && given != value {
+            return false;
+        }
+    }
+
+    let side = puzzle.side() as u8;
+    let mut seen = opening.values.clone();
+    seen.sort_unstable();
+    seen == (1..=side).collect::<Vec<u8>>()
+}
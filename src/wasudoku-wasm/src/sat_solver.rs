@@ -0,0 +1,420 @@
+/*
+* Copyright (C) 2025  Henrique Almeida
+* This file is part of WASudoku.
+
+* WASudoku is free software: you can redistribute it and/or modify
+* it under the terms of the GNU Affero General Public License as published
+* by the Free Software Foundation, either version 3 of the License, or
+* (at your option) any later version.
+
+* WASudoku is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+* GNU Affero General Public License for more details.
+
+* You should have received a copy of the GNU Affero General Public License
+* along with WASudoku.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! An alternative CNF/SAT solver backend, used for uniqueness checks on
+//! puzzles that are too sparse for plain backtracking to check quickly.
+
+use crate::board::Board;
+
+/// A CNF clause: a disjunction of literals. A positive literal `v` means
+/// variable `v` must be true; a negative literal `-v` means it must be false.
+type Clause = Vec<i32>;
+
+/// The CNF encoding of a board's constraints, plus the variable numbering
+/// needed to decode an assignment back into cells.
+struct Encoding {
+    side: usize,
+    clauses: Vec<Clause>,
+}
+
+/// The boolean variable for "cell `cell` holds `value`" (`1..=side`).
+fn var(cell: usize, value: u8, side: usize) -> i32 {
+    (cell * side + (value as usize - 1) + 1) as i32
+}
+
+/// Encode a board into CNF: one variable per (cell, value), with clauses
+/// enforcing that every cell holds exactly one value and every row, column,
+/// and box contains each value exactly once, plus unit clauses for givens.
+fn encode(board: &Board) -> Encoding {
+    let order = board.order as usize;
+    let side = order * order;
+    let mut clauses = Vec::new();
+
+    // Each cell holds at least one value, and at most one value.
+    for cell in 0..side * side {
+        clauses.push((1..=side as u8).map(|v| var(cell, v, side)).collect());
+        for v1 in 1..=side as u8 {
+            for v2 in (v1 + 1)..=side as u8 {
+                clauses.push(vec![-var(cell, v1, side), -var(cell, v2, side)]);
+            }
+        }
+    }
+
+    // Each unit (row, column, box) contains every value exactly once.
+    let units = build_units(order);
+    for unit in &units {
+        for v in 1..=side as u8 {
+            clauses.push(unit.iter().map(|&cell| var(cell, v, side)).collect());
+            for i in 0..unit.len() {
+                for j in (i + 1)..unit.len() {
+                    clauses.push(vec![-var(unit[i], v, side), -var(unit[j], v, side)]);
+                }
+            }
+        }
+    }
+
+    // Unit clauses for the givens.
+    for (cell, &value) in board.cells.iter().enumerate() {
+        if value != 0 {
+            clauses.push(vec![var(cell, value, side)]);
+        }
+    }
+
+    Encoding { side, clauses }
+}
+
+/// Build the row, column, and box units for the given board `order`.
+fn build_units(order: usize) -> Vec<Vec<usize>> {
+    let side = order * order;
+    let mut rows = vec![Vec::with_capacity(side); side];
+    let mut cols = vec![Vec::with_capacity(side); side];
+    let mut boxes = vec![Vec::with_capacity(side); side];
+
+    for row in 0..side {
+        for col in 0..side {
+            let cell = row * side + col;
+            let box_index = (row / order) * order + (col / order);
+            rows[row].push(cell);
+            cols[col].push(cell);
+            boxes[box_index].push(cell);
+        }
+    }
+
+    let mut units = Vec::with_capacity(side * 3);
+    units.extend(rows);
+    units.extend(cols);
+    units.extend(boxes);
+    units
+}
+
+/// A partial variable assignment: `None` is unassigned, `Some(true/false)` is decided.
+type Assignment = Vec<Option<bool>>;
+
+/// Evaluate a literal under the current assignment.
+fn literal_value(lit: i32, assignment: &Assignment) -> Option<bool> {
+    let value = assignment[lit.unsigned_abs() as usize]?;
+    Some(if lit > 0 { value } else { !value })
+}
+
+/// Decode a fully-assigned variable vector back into board cells.
+fn decode(assignment: &Assignment, side: usize, cell_count: usize) -> Vec<u8> {
+    let mut cells = vec![0u8; cell_count];
+    for cell in 0..cell_count {
+        for value in 1..=side as u8 {
+            if assignment[var(cell, value, side) as usize] == Some(true) {
+                cells[cell] = value;
+                break;
+            }
+        }
+    }
+    cells
+}
+
+/// A CDCL (conflict-driven clause learning) solver over the same CNF
+/// encoding [`encode`] produces. Unlike plain chronological backtracking, a
+/// conflict triggers first-UIP clause learning and a non-chronological
+/// backjump straight to the level where the learned clause becomes unit,
+/// and branching is guided by VSIDS-style activity
+/// scores instead of variable order. This makes it far less prone to
+/// thrashing on the deeply-constrained, sparse-clue puzzles where plain
+/// backtracking can stall.
+struct Cdcl {
+    /// The original clauses plus every clause learned so far.
+    clauses: Vec<Clause>,
+    /// `assignment[v]` is `v`'s current truth value, or `None` if unassigned.
+    assignment: Assignment,
+    /// The decision level `v` was assigned at, or `-1` if unassigned.
+    level: Vec<i32>,
+    /// The clause (by index into `clauses`) that forced `v`'s assignment via
+    /// unit propagation, or `None` if `v` is unassigned or was a decision.
+    reason: Vec<Option<usize>>,
+    /// Assigned literals in chronological order.
+    trail: Vec<i32>,
+    /// `trail.len()` at the start of each decision level.
+    trail_lim: Vec<usize>,
+    /// VSIDS activity score per variable; bumped on conflict involvement and
+    /// periodically decayed so recent conflicts dominate the branching order.
+    activity: Vec<f64>,
+    /// The amount to bump a variable's activity by; grows every conflict
+    /// (equivalent to decaying every other variable's activity instead).
+    activity_bump: f64,
+}
+
+impl Cdcl {
+    fn new(num_vars: usize, clauses: Vec<Clause>) -> Self {
+        Cdcl {
+            clauses,
+            assignment: vec![None; num_vars + 1],
+            level: vec![-1; num_vars + 1],
+            reason: vec![None; num_vars + 1],
+            trail: Vec::new(),
+            trail_lim: Vec::new(),
+            activity: vec![0.0; num_vars + 1],
+            activity_bump: 1.0,
+        }
+    }
+
+    fn current_level(&self) -> usize {
+        self.trail_lim.len()
+    }
+
+    fn assign(&mut self, var: usize, value: bool, level: usize, reason: Option<usize>) {
+        self.assignment[var] = Some(value);
+        self.level[var] = level as i32;
+        self.reason[var] = reason;
+        self.trail.push(if value { var as i32 } else { -(var as i32) });
+    }
+
+    /// Propagate unit clauses to a fixed point, recording the forcing clause
+    /// as each variable's reason. Returns the index of the clause that went
+    /// unsatisfiable, if any.
+    fn propagate(&mut self) -> Option<usize> {
+        loop {
+            let mut changed = false;
+            for idx in 0..self.clauses.len() {
+                let mut unassigned_lit = None;
+                let mut satisfied = false;
+                let mut unassigned_count = 0;
+
+                for &lit in &self.clauses[idx] {
+                    match literal_value(lit, &self.assignment) {
+                        Some(true) => {
+                            satisfied = true;
+                            break;
+                        }
+                        Some(false) => {}
+                        None => {
+                            unassigned_count += 1;
+                            unassigned_lit = Some(lit);
+                        }
+                    }
+                }
+
+                if satisfied {
+                    continue;
+                }
+                if unassigned_count == 0 {
+                    return Some(idx); // Conflict: every literal is false.
+                }
+                if unassigned_count == 1 {
+                    let lit = unassigned_lit.unwrap();
+                    let var = lit.unsigned_abs() as usize;
+                    if self.assignment[var].is_none() {
+                        self.assign(var, lit > 0, self.current_level(), Some(idx));
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                return None;
+            }
+        }
+    }
+
+    fn bump_activity(&mut self, var: usize) {
+        self.activity[var] += self.activity_bump;
+    }
+
+    fn decay_activity(&mut self) {
+        // Growing the bump amount is equivalent to (and cheaper than) scaling
+        // every activity down after each conflict.
+        self.activity_bump /= 0.95;
+    }
+
+    /// First-UIP conflict analysis: resolve the conflicting clause against
+    /// the reason clause of each variable assigned at the current decision
+    /// level, walking the trail backwards, until only one literal from the
+    /// current level remains (the unique implication point). Returns the
+    /// learned clause and the level to backjump to (the second-highest level
+    /// among the clause's other literals, or `0` if there are none).
+    fn analyze(&mut self, conflict_idx: usize) -> (Clause, usize) {
+        let mut seen = vec![false; self.assignment.len()];
+        let mut learned: Clause = Vec::new();
+        let current_level = self.current_level() as i32;
+        let mut pending_at_current_level = 0usize;
+        let mut clause = self.clauses[conflict_idx].clone();
+        let mut trail_idx = self.trail.len();
+        let mut uip_lit: Option<i32> = None;
+
+        loop {
+            for &lit in &clause {
+                let var = lit.unsigned_abs() as usize;
+                if seen[var] {
+                    continue;
+                }
+                seen[var] = true;
+                self.bump_activity(var);
+                if self.level[var] == current_level {
+                    pending_at_current_level += 1;
+                } else if self.level[var] > 0 {
+                    // An earlier-level literal (false under the current
+                    // assignment) belongs in the learned clause as is.
+                    learned.push(lit);
+                }
+            }
+
+            // Walk the trail backwards to the next literal we still need to resolve.
+            loop {
+                trail_idx -= 1;
+                let lit = self.trail[trail_idx];
+                let var = lit.unsigned_abs() as usize;
+                if seen[var] {
+                    // `seen` stays set, so this variable's own literal in its
+                    // reason clause is skipped when that clause is resolved.
+                    uip_lit = Some(lit);
+                    break;
+                }
+            }
+
+            pending_at_current_level -= 1;
+            if pending_at_current_level == 0 {
+                break;
+            }
+            let var = uip_lit.unwrap().unsigned_abs() as usize;
+            clause = self.clauses[self.reason[var].expect(
+                "a literal still pending resolution at the current level must have been propagated",
+            )]
+            .clone();
+        }
+
+        let asserted = -uip_lit.unwrap();
+        learned.push(asserted);
+
+        let backtrack_level = learned
+            .iter()
+            .filter(|&&lit| lit != asserted)
+            .map(|&lit| self.level[lit.unsigned_abs() as usize] as usize)
+            .max()
+            .unwrap_or(0);
+
+        (learned, backtrack_level)
+    }
+
+    fn backtrack_to(&mut self, level: usize) {
+        let target_len = self.trail_lim[level];
+        while self.trail.len() > target_len {
+            let lit = self.trail.pop().unwrap();
+            let var = lit.unsigned_abs() as usize;
+            self.assignment[var] = None;
+            self.level[var] = -1;
+            self.reason[var] = None;
+        }
+        self.trail_lim.truncate(level);
+    }
+
+    /// Pick the unassigned variable with the highest VSIDS activity, and
+    /// open a new decision level by assigning it `true`. Returns `false` if
+    /// every variable is already assigned (the formula is satisfied).
+    fn decide(&mut self) -> bool {
+        let next = (1..self.assignment.len())
+            .filter(|&v| self.assignment[v].is_none())
+            .max_by(|&a, &b| self.activity[a].partial_cmp(&self.activity[b]).unwrap());
+
+        match next {
+            None => false,
+            Some(var) => {
+                self.trail_lim.push(self.trail.len());
+                let level = self.current_level();
+                self.assign(var, true, level, None);
+                true
+            }
+        }
+    }
+
+    /// Run the CDCL loop to completion, returning `true` if satisfiable
+    /// (leaving `self.assignment` as the model) or `false` if the formula is
+    /// unsatisfiable.
+    fn solve(&mut self) -> bool {
+        loop {
+            match self.propagate() {
+                Some(conflict_idx) => {
+                    if self.current_level() == 0 {
+                        return false;
+                    }
+                    let (learned, backtrack_level) = self.analyze(conflict_idx);
+                    let asserted = *learned.last().unwrap();
+                    self.clauses.push(learned);
+                    let learned_idx = self.clauses.len() - 1;
+                    self.backtrack_to(backtrack_level);
+                    self.decay_activity();
+                    let var = asserted.unsigned_abs() as usize;
+                    self.assign(var, asserted > 0, backtrack_level, Some(learned_idx));
+                }
+                None => {
+                    if !self.decide() {
+                        return true;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Solve a board using the CDCL backend, returning the first model found.
+pub fn cdcl_solve(board: &Board) -> Option<Board> {
+    let encoding = encode(board);
+    let num_vars = board.cells.len() * encoding.side;
+    let mut cdcl = Cdcl::new(num_vars, encoding.clauses);
+
+    if !cdcl.solve() {
+        return None;
+    }
+    Some(Board::from_cells_unchecked(
+        board.order,
+        decode(&cdcl.assignment, encoding.side, board.cells.len()),
+    ))
+}
+
+/// Count solutions to a board using the CDCL backend, stopping once `limit`
+/// solutions have been found (blocking each found model by adding its
+/// negation as a new clause and re-solving). Intended as the
+/// uniqueness-check fallback for very sparse boards where MRV backtracking's
+/// node count blows up.
+pub fn cdcl_count_solutions(board: &Board, limit: usize) -> usize {
+    if limit == 0 {
+        return 0;
+    }
+
+    let encoding = encode(board);
+    let num_vars = board.cells.len() * encoding.side;
+    let mut clauses = encoding.clauses;
+    let mut count = 0;
+
+    loop {
+        let mut cdcl = Cdcl::new(num_vars, clauses.clone());
+        if !cdcl.solve() {
+            return count;
+        }
+
+        count += 1;
+        if count >= limit {
+            return count;
+        }
+
+        let blocking: Clause = (1..=num_vars as i32)
+            .map(|v| {
+                if cdcl.assignment[v as usize] == Some(true) {
+                    -v
+                } else {
+                    v
+                }
+            })
+            .collect();
+        clauses.push(blocking);
+    }
+}
@@ -17,6 +17,15 @@
 */
 
 use crate::board::Board;
+use crate::sat_solver;
+use std::fmt;
+
+/// How many recursive calls [`count_solutions`] allows itself before giving
+/// up on MRV backtracking and handing the board to the CDCL SAT backend
+/// instead. Sparse, minimal-clue puzzles can make backtracking thrash
+/// through a huge number of dead branches; CDCL's clause learning avoids
+/// re-discovering the same conflict twice.
+const COUNT_SOLUTIONS_NODE_BUDGET: usize = 50_000;
 
 /// The outcome of searching for the next cell to solve.
 enum FindResult {
@@ -48,16 +57,17 @@ pub fn solve(board: &mut Board) -> bool {
         FindResult::Solved => true,
         FindResult::Unsolvable => false,
         FindResult::Cell(row, col) => {
-            for num in 1..=9 {
+            let side = board.side();
+            for num in 1..=side as u8 {
                 if board.is_valid_move(row, col, num) {
-                    board.cells[row * 9 + col] = num;
+                    board.set(row * side + col, num);
 
                     if solve(board) {
                         return true;
                     }
 
                     // Backtrack if the path did not lead to a solution.
-                    board.cells[row * 9 + col] = 0;
+                    board.set(row * side + col, 0);
                 }
             }
             // Trigger further backtracking if no number works for this cell.
@@ -68,18 +78,19 @@ pub fn solve(board: &mut Board) -> bool {
 
 /// Solve a Sudoku puzzle using backtracking with a randomized number order.
 /// Used for generating a variety of solved boards.
-pub fn solve_randomized(board: &mut Board, numbers: &[u8; 9]) -> bool {
+pub fn solve_randomized(board: &mut Board, numbers: &[u8]) -> bool {
     match find_most_constrained_cell(board) {
         FindResult::Solved => true,
         FindResult::Unsolvable => false,
         FindResult::Cell(row, col) => {
+            let side = board.side();
             for &num in numbers {
                 if board.is_valid_move(row, col, num) {
-                    board.cells[row * 9 + col] = num;
+                    board.set(row * side + col, num);
                     if solve_randomized(board, numbers) {
                         return true;
                     }
-                    board.cells[row * 9 + col] = 0; // Backtrack
+                    board.set(row * side + col, 0); // Backtrack
                 }
             }
             false
@@ -87,63 +98,142 @@ pub fn solve_randomized(board: &mut Board, numbers: &[u8; 9]) -> bool {
     }
 }
 
-/// Count the number of solutions for a given board. Stops counting if more than 1 solution is found.
+/// Why [`solve_checked`] could not return a single definitive solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveError {
+    /// The board was malformed before solving was even attempted (e.g. it
+    /// failed to parse). `solve_checked` never returns this itself, since it
+    /// takes an already-parsed `Board`; it exists so callers parsing
+    /// untrusted input (like the wasm boundary) can report it alongside the
+    /// other outcomes as one uniform error type.
+    InvalidInput,
+    /// The board has no valid completion.
+    Unsolvable,
+    /// The board has more than one valid completion, so no single answer
+    /// can be reported as *the* solution.
+    MultipleSolutions,
+    /// The underlying search panicked (only reachable through
+    /// `catch_unwind` at the wasm boundary).
+    SolverPanic,
+}
+
+impl SolveError {
+    /// A short, user-facing description of this outcome.
+    pub fn message(&self) -> &'static str {
+        match self {
+            SolveError::InvalidInput => "Invalid puzzle: the input board is malformed.",
+            SolveError::Unsolvable => "No solution exists for the given puzzle.",
+            SolveError::MultipleSolutions => "The puzzle has more than one solution.",
+            SolveError::SolverPanic => "Solver crashed due to a critical error.",
+        }
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+/// Solve `board`, distinguishing "no solution" ([`SolveError::Unsolvable`])
+/// from "ambiguous puzzle" ([`SolveError::MultipleSolutions`]) instead of
+/// collapsing both into a single `false` the way [`solve`] does.
+///
+/// Runs [`count_solutions`] first to make that distinction, since [`solve`]
+/// itself stops at the first solution it finds and so cannot tell a unique
+/// solution from one of several.
+pub fn solve_checked(board: &Board) -> Result<Board, SolveError> {
+    match count_solutions(board) {
+        0 => Err(SolveError::Unsolvable),
+        1 => {
+            let mut solved = board.clone();
+            if solve(&mut solved) {
+                Ok(solved)
+            } else {
+                // count_solutions and solve agree on reachability, so this
+                // should not happen; treat it as unsolvable rather than panic.
+                Err(SolveError::Unsolvable)
+            }
+        }
+        _ => Err(SolveError::MultipleSolutions),
+    }
+}
+
+/// Count the number of solutions for a given board. Stops counting if more
+/// than 1 solution is found. Falls back to the CDCL SAT backend
+/// (`sat_solver::cdcl_count_solutions`) if MRV backtracking exceeds
+/// [`COUNT_SOLUTIONS_NODE_BUDGET`] nodes without finishing, since very
+/// sparse boards can make plain backtracking thrash.
 pub fn count_solutions(board: &Board) -> u8 {
     let mut counter = 0;
-    let mut board_clone = *board;
-    count_solutions_recursive(&mut board_clone, &mut counter);
-    counter
+    let mut nodes = 0;
+    let mut board_clone = board.clone();
+    if count_solutions_recursive(&mut board_clone, &mut counter, &mut nodes) {
+        counter
+    } else {
+        sat_solver::cdcl_count_solutions(board, 2) as u8
+    }
 }
 
-fn count_solutions_recursive(board: &mut Board, counter: &mut u8) {
+/// Returns `false` if the node budget was exceeded before the search
+/// finished, in which case `counter`'s value is incomplete and must be
+/// discarded by the caller.
+fn count_solutions_recursive(board: &mut Board, counter: &mut u8, nodes: &mut usize) -> bool {
     if *counter > 1 {
-        return;
+        return true;
+    }
+
+    *nodes += 1;
+    if *nodes > COUNT_SOLUTIONS_NODE_BUDGET {
+        return false;
     }
 
     match find_most_constrained_cell(board) {
         FindResult::Solved => {
             *counter += 1;
+            true
         }
-        FindResult::Unsolvable => (),
+        FindResult::Unsolvable => true,
         FindResult::Cell(row, col) => {
-            for num in 1..=9 {
+            let side = board.side();
+            for num in 1..=side as u8 {
                 if board.is_valid_move(row, col, num) {
-                    board.cells[row * 9 + col] = num;
-                    count_solutions_recursive(board, counter);
+                    board.set(row * side + col, num);
+                    let within_budget = count_solutions_recursive(board, counter, nodes);
+                    if !within_budget {
+                        board.set(row * side + col, 0);
+                        return false;
+                    }
                     if *counter > 1 {
-                        return;
+                        board.set(row * side + col, 0);
+                        return true;
                     }
                 }
             }
-            board.cells[row * 9 + col] = 0; // Backtrack
+            board.set(row * side + col, 0); // Backtrack
+            true
         }
     }
 }
 
-
-/// Count the number of valid moves (1-9) for a given cell.
-fn count_possibilities(board: &Board, row: usize, col: usize) -> u8 {
-    let mut possibilities = 0;
-    for num in 1..=9 {
-        if board.is_valid_move(row, col, num) {
-            possibilities += 1;
-        }
-    }
-    possibilities
+/// Count the number of valid moves (1..=side) for a given cell.
+fn count_possibilities(board: &Board, row: usize, col: usize) -> u32 {
+    board.candidates(row, col).count_ones()
 }
 
 /// Find the empty cell with the fewest valid moves (Minimum Remaining Values heuristic).
 fn find_most_constrained_cell(board: &Board) -> FindResult {
+    let side = board.side();
     let mut best_cell: Option<(usize, usize)> = None;
-    let mut min_possibilities = 10;
+    let mut min_possibilities = side as u32 + 1;
 
-    for i in 0..81 {
+    for i in 0..side * side {
         if board.cells[i] != 0 {
             continue;
         }
 
-        let row = i / 9;
-        let col = i % 9;
+        let row = i / side;
+        let col = i % side;
         let possibilities = count_possibilities(board, row, col);
 
         // An empty cell with zero possibilities means the board is unsolvable.
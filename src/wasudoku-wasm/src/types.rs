@@ -27,6 +27,27 @@ pub struct SolveResult {
     pub solution: Option<String>,
 }
 
+/// The result of an interactive `GameSession` move operation (`make_move`,
+/// `undo`, `redo`).
+#[derive(Serialize, Clone)]
+pub struct MoveResult {
+    /// The board after the attempted operation, as an encoded string.
+    pub board: String,
+    /// For `make_move`, whether the placement conflicted with the board's
+    /// rules and was left unapplied. For `undo`/`redo`, whether there was no
+    /// move left to undo/redo.
+    pub conflict: bool,
+}
+
+/// A puzzle (and its embedded solution, if any) loaded from a KSudoku file.
+#[derive(Serialize, Clone)]
+pub struct KsudokuLoadResult {
+    /// The puzzle board as an encoded string.
+    pub puzzle: String,
+    /// The embedded solution board as an encoded string, if the file had one.
+    pub solution: Option<String>,
+}
+
 /// A single logical step in solving the puzzle.
 #[derive(Serialize, Clone)]
 pub struct SolvingStep {
@@ -28,7 +28,7 @@ fn solved_board() -> Board {
 #[test]
 fn test_is_valid_move_true_for_empty_spot() {
     let mut board = solved_board();
-    board.cells[0] = 0; // Make top-left empty
+    board.set(0, 0); // Make top-left empty
     assert!(board.is_valid_move(0, 0, 5));
 }
 
@@ -53,6 +53,28 @@ fn test_is_valid_move_false_for_box_conflict() {
     assert!(!board.is_valid_move(0, 2, 7));
 }
 
+#[test]
+fn test_set_updates_masks_for_is_valid_move() {
+    let mut board = solved_board();
+    // (0,0) holds 5; clearing it should free up 5 in its row, column and box.
+    board.set(0, 0);
+    assert!(board.is_valid_move(0, 0, 5));
+
+    // Putting it back should close that candidate again.
+    board.set(0, 5);
+    assert!(!board.is_valid_move(0, 1, 5));
+}
+
+#[test]
+fn test_candidates_excludes_used_digits() {
+    let mut board = solved_board();
+    board.set(0, 0);
+    let candidates = board.candidates(0, 0);
+
+    // Only the digit that used to live here (5) should be a candidate again.
+    assert_eq!(candidates, 1 << 4);
+}
+
 #[test]
 fn test_display_board() {
     let puzzle_str =
@@ -0,0 +1,84 @@
+/*
+* Copyright (C) 2025  Henrique Almeida
+* This file is part of WASudoku.
+
+* WASudoku is free software: you can redistribute it and/or modify
+* it under the terms of the GNU Affero General Public License as published
+* by the Free Software Foundation, either version 3 of the License, or
+* (at your option) any later version.
+
+* WASudoku is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+* GNU Affero General Public License for more details.
+
+* You should have received a copy of the GNU Affero General Public License
+* along with WASudoku.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use wasudoku_wasm::board::Board;
+use wasudoku_wasm::constraints;
+use wasudoku_wasm::generate::{self, Difficulty, Variant};
+use wasudoku_wasm::solver;
+
+#[test]
+fn test_diagonal_constraint_rejects_repeated_digit_on_diagonal() {
+    let mut board = Board::empty(3);
+    board.constraints = constraints::diagonal_groups(&board);
+
+    board.set(0, 5); // (0, 0), on the main diagonal.
+    assert!(!board.is_valid_move(4, 4, 5), "repeats 5 on the main diagonal");
+    assert!(board.is_valid_move(4, 4, 3));
+}
+
+#[test]
+fn test_windoku_constraint_rejects_repeated_digit_in_extra_region() {
+    let mut board = Board::empty(3);
+    board.constraints = constraints::windoku_groups(&board);
+
+    board.set(10, 7); // (1, 1), inside the first extra Windoku region.
+    assert!(
+        !board.is_valid_move(3, 3, 7),
+        "repeats 7 within the same extra Windoku region, though (1,1) and (3,3) share no row, column or box"
+    );
+}
+
+#[test]
+fn test_windoku_constraint_empty_for_non_classic_order() {
+    let board = Board::empty(2);
+    assert!(constraints::windoku_groups(&board).is_empty());
+}
+
+#[test]
+fn test_killer_cage_enforces_target_sum_and_uniqueness() {
+    let mut board = Board::empty(3);
+    board.constraints = constraints::killer_cages(vec![(vec![0, 1, 2], 6)]);
+
+    board.set(0, 1);
+    assert!(!board.is_valid_move(0, 1, 1), "repeats 1 within the cage");
+    assert!(
+        !board.is_valid_move(0, 1, 6),
+        "1 + 6 already exceeds the target sum of 6"
+    );
+    assert!(board.is_valid_move(0, 1, 2));
+}
+
+#[test]
+fn test_generate_variant_diagonal_has_unique_solution() {
+    let puzzle = generate::generate_variant(3, Difficulty::Easy, Variant::Diagonal);
+    assert_eq!(
+        solver::count_solutions(&puzzle),
+        1,
+        "diagonal variant puzzle must have exactly one solution"
+    );
+}
+
+#[test]
+fn test_generate_variant_windoku_has_unique_solution() {
+    let puzzle = generate::generate_variant(3, Difficulty::Easy, Variant::Windoku);
+    assert_eq!(
+        solver::count_solutions(&puzzle),
+        1,
+        "windoku variant puzzle must have exactly one solution"
+    );
+}
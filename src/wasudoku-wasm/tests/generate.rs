@@ -120,3 +120,86 @@ fn test_generate_extreme_puzzle_difficulty() {
         clue_count
     );
 }
+
+#[test]
+fn test_generate_with_seed_is_deterministic() {
+    let (first, seed) = generate::generate_with_seed(Difficulty::Easy, 42);
+    let (second, effective_seed) = generate::generate_with_seed(Difficulty::Easy, 42);
+
+    assert_eq!(effective_seed, seed);
+    assert_eq!(first.to_string(), second.to_string());
+}
+
+#[test]
+fn test_generate_with_seed_differs_across_seeds() {
+    let (first, _) = generate::generate_with_seed(Difficulty::Easy, 1);
+    let (second, _) = generate::generate_with_seed(Difficulty::Easy, 2);
+
+    assert_ne!(first.to_string(), second.to_string());
+}
+
+#[test]
+fn test_generate_to_level_matches_requested_level() {
+    let (puzzle, level) = generate::generate_to_level(TechniqueLevel::Intermediate, 7);
+
+    assert_eq!(level, TechniqueLevel::Intermediate);
+
+    assert_eq!(
+        solver::count_solutions(&puzzle),
+        1,
+        "Generated puzzle must have exactly one solution."
+    );
+
+    let (measured_level, _) = logical_solver::get_difficulty(&puzzle);
+    assert_eq!(measured_level, TechniqueLevel::Intermediate);
+}
+
+#[test]
+fn test_generate_to_level_is_deterministic() {
+    let (first, _) = generate::generate_to_level(TechniqueLevel::Basic, 99);
+    let (second, _) = generate::generate_to_level(TechniqueLevel::Basic, 99);
+
+    assert_eq!(first.to_string(), second.to_string());
+}
+
+#[test]
+fn test_generate_scored_respects_the_requested_band() {
+    let puzzle = generate::generate_scored(3, 30, 200);
+
+    assert_eq!(
+        solver::count_solutions(&puzzle),
+        1,
+        "Generated puzzle must have exactly one solution."
+    );
+
+    let score = logical_solver::difficulty_score(&puzzle);
+    assert!(
+        (30..=200).contains(&score),
+        "Score {} should fall within the requested 30..=200 band",
+        score
+    );
+}
+
+#[test]
+fn test_generate_hard_puzzle_score_is_in_band() {
+    let puzzle = generate::generate(Difficulty::Hard);
+    let score = logical_solver::difficulty_score(&puzzle);
+
+    assert!(
+        (30..=200).contains(&score),
+        "Hard puzzle score {} should fall within the hard-tier band",
+        score
+    );
+}
+
+#[test]
+fn test_generate_extreme_puzzle_score_clears_the_floor() {
+    let puzzle = generate::generate(Difficulty::Extreme);
+    let score = logical_solver::difficulty_score(&puzzle);
+
+    assert!(
+        score >= 80,
+        "Extreme puzzle score {} should clear the extreme-tier floor",
+        score
+    );
+}
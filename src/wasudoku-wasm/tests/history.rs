@@ -0,0 +1,114 @@
+/*
+* Copyright (C) 2025  Henrique Almeida
+* This file is part of WASudoku.
+
+* WASudoku is free software: you can redistribute it and/or modify
+* it under the terms of the GNU Affero General Public License as published
+* by the Free Software Foundation, either version 3 of the License, or
+* (at your option) any later version.
+
+* WASudoku is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+* GNU Affero General Public License for more details.
+
+* You should have received a copy of the GNU Affero General Public License
+* along with WASudoku.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use wasudoku_wasm::board::Board;
+use wasudoku_wasm::history::History;
+
+fn puzzle_with_blank_first_cell() -> Board {
+    let puzzle_str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+    let mut board = Board::from_str(puzzle_str).unwrap();
+    board.set(0, 0); // (0, 0) was '5'; clear it so it can be played into.
+    board
+}
+
+#[test]
+fn test_apply_move_places_a_valid_digit() {
+    let mut board = puzzle_with_blank_first_cell();
+    let mut history = History::new();
+
+    assert!(history.apply_move(&mut board, 0, 5));
+    assert_eq!(board.cells[0], 5);
+}
+
+#[test]
+fn test_apply_move_rejects_a_conflicting_digit() {
+    let mut board = puzzle_with_blank_first_cell();
+    let mut history = History::new();
+
+    // (0, 1) already holds '3', so placing it at (0, 0) conflicts on the row.
+    assert!(!history.apply_move(&mut board, 0, 3));
+    assert_eq!(board.cells[0], 0, "a rejected move must leave the board unchanged");
+}
+
+#[test]
+fn test_history_undo_restores_prior_value() {
+    let mut board = puzzle_with_blank_first_cell();
+    let mut history = History::new();
+
+    history.apply_move(&mut board, 0, 5);
+    assert!(history.undo(&mut board));
+    assert_eq!(board.cells[0], 0);
+}
+
+#[test]
+fn test_history_redo_reapplies_an_undone_move() {
+    let mut board = puzzle_with_blank_first_cell();
+    let mut history = History::new();
+
+    history.apply_move(&mut board, 0, 5);
+    history.undo(&mut board);
+    assert!(history.redo(&mut board));
+    assert_eq!(board.cells[0], 5);
+}
+
+#[test]
+fn test_apply_move_rejects_an_out_of_range_index() {
+    let mut board = puzzle_with_blank_first_cell();
+    let mut history = History::new();
+    let out_of_range = board.cells.len();
+
+    assert!(!history.apply_move(&mut board, out_of_range, 5));
+}
+
+#[test]
+fn test_history_undo_on_empty_stack_returns_false() {
+    let mut board = puzzle_with_blank_first_cell();
+    let mut history = History::new();
+    assert!(!history.undo(&mut board));
+}
+
+#[test]
+fn test_history_new_move_clears_the_redo_stack() {
+    let mut board = puzzle_with_blank_first_cell();
+    let mut history = History::new();
+
+    history.apply_move(&mut board, 0, 5);
+    history.undo(&mut board);
+    // A fresh move should drop the now-stale redo entry for the old move.
+    history.apply_move(&mut board, 0, 5);
+    assert!(!history.redo(&mut board));
+}
+
+#[test]
+fn test_history_backtrack_through_multiple_moves() {
+    let mut board = puzzle_with_blank_first_cell();
+    let mut history = History::new();
+    board.set(1, 0); // Also clear (0, 1), which held '3'.
+
+    history.apply_move(&mut board, 0, 5);
+    history.apply_move(&mut board, 1, 3);
+    assert_eq!(board.cells[0], 5);
+    assert_eq!(board.cells[1], 3);
+
+    assert!(history.undo(&mut board));
+    assert_eq!(board.cells[1], 0);
+    assert!(history.undo(&mut board));
+    assert_eq!(board.cells[0], 0);
+    assert!(!history.undo(&mut board));
+}
@@ -0,0 +1,99 @@
+/*
+* Copyright (C) 2025  Henrique Almeida
+* This file is part of WASudoku.
+
+* WASudoku is free software: you can redistribute it and/or modify
+* it under the terms of the GNU Affero General Public License as published
+* by the Free Software Foundation, either version 3 of the License, or
+* (at your option) any later version.
+
+* WASudoku is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+* GNU Affero General Public License for more details.
+
+* You should have received a copy of the GNU Affero General Public License
+* along with WASudoku.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use wasudoku_wasm::board::Board;
+
+fn sample_ksudoku() -> String {
+    let puzzle_str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+    let board = Board::from_str(puzzle_str).unwrap();
+    board.to_ksudoku(9, None)
+}
+
+#[test]
+fn test_ksudoku_round_trip_without_solution() {
+    let file = sample_ksudoku();
+    let (puzzle, solution) = Board::from_ksudoku(&file).unwrap();
+
+    assert_eq!(puzzle.order, 3);
+    assert!(solution.is_none());
+}
+
+#[test]
+fn test_ksudoku_round_trip_with_solution() {
+    let solution_str =
+        "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+    let solution_board = Board::from_str(solution_str).unwrap();
+    let mut file = sample_ksudoku();
+    file.push_str(&format!(
+        "Solution: {}\n",
+        solution_board
+            .to_string()
+            .chars()
+            .map(|c| if c == '.' {
+                '_'
+            } else {
+                (c.to_digit(10).unwrap() as u8 + b'a') as char
+            })
+            .collect::<String>()
+    ));
+
+    let (_, solution) = Board::from_ksudoku(&file).unwrap();
+    assert_eq!(solution.unwrap().to_string(), solution_str);
+}
+
+#[test]
+fn test_ksudoku_to_ksudoku_embeds_solution() {
+    let puzzle_str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+    let solution_str =
+        "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+    let puzzle_board = Board::from_str(puzzle_str).unwrap();
+    let solution_board = Board::from_str(solution_str).unwrap();
+
+    let file = puzzle_board.to_ksudoku(9, Some(&solution_board));
+    let (puzzle, solution) = Board::from_ksudoku(&file).unwrap();
+
+    assert_eq!(puzzle.to_string(), puzzle_str);
+    assert_eq!(solution.unwrap().to_string(), solution_str);
+}
+
+#[test]
+fn test_ksudoku_rejects_missing_fields() {
+    assert!(Board::from_ksudoku("Type: Plain\n").is_err());
+}
+
+#[test]
+fn test_ksudoku_rejects_invalid_character() {
+    let file = "Type: Plain\nOrder: 9\nPuzzle: !!!...............................................................................\n";
+    assert!(Board::from_ksudoku(file).is_err());
+}
+
+#[test]
+fn test_ksudoku_rejects_puzzle_shorter_than_declared_order() {
+    // `Order: 9` needs 81 characters; this `Puzzle` has only 80.
+    let file = "Type: Plain\nOrder: 9\nPuzzle: ________________________________________________________________________________\n";
+    assert!(Board::from_ksudoku(file).is_err());
+}
+
+#[test]
+fn test_ksudoku_rejects_puzzle_longer_than_declared_order() {
+    // `Order: 9` needs 81 characters; this `Puzzle` has 82.
+    let file = "Type: Plain\nOrder: 9\nPuzzle: __________________________________________________________________________________\n";
+    assert!(Board::from_ksudoku(file).is_err());
+}
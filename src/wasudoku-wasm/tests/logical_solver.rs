@@ -17,7 +17,7 @@
 */
 
 use wasudoku_wasm::board::Board;
-use wasudoku_wasm::logical_solver::{self, LogicalBoard};
+use wasudoku_wasm::logical_solver::{self, LogicalBoard, TechniqueLevel};
 use wasudoku_wasm::solver;
 use wasudoku_wasm::types::{Elimination, SolvingStep};
 
@@ -184,6 +184,127 @@ fn test_hybrid_solver_logic_solves_puzzle() {
     assert_eq!(result.unwrap().to_string(), solution_str);
 }
 
+#[test]
+fn test_difficulty_score_is_zero_for_already_solved_board() {
+    let solution_str =
+        "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+    let board = Board::from_str(solution_str).unwrap();
+    assert_eq!(logical_solver::difficulty_score(&board), 0);
+}
+
+#[test]
+fn test_difficulty_score_is_positive_for_solvable_puzzle() {
+    let puzzle_str =
+        "...2..7...5..96832.8.7....641.....78.2..745..7.31854....2531..4.3164..5...9...61.";
+    let board = Board::from_str(puzzle_str).unwrap();
+    let (level, _) = logical_solver::get_difficulty(&board);
+
+    assert!(logical_solver::difficulty_score(&board) > 0);
+    assert_eq!(level, TechniqueLevel::Basic);
+}
+
+#[test]
+fn test_probe_does_not_panic_on_stalled_puzzle() {
+    // A puzzle that pure singles/subsets/fish logic cannot finish. Probe's
+    // single-cell heuristic doesn't trip a contradiction on it either, so
+    // once WXYZ-Wing and Forcing Chain are restricted to sound eliminations
+    // this puzzle legitimately makes no logical progress at all; completing
+    // it needs the backtracking fallback, covered separately by
+    // `test_solve_with_steps_and_guesses_solves_a_stalled_puzzle`. The
+    // invariant here is just that solving never panics and never touches a
+    // given clue.
+    let puzzle_str =
+        "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..";
+    let board = Board::from_str(puzzle_str).unwrap();
+    let (_, final_board) = logical_solver::solve_with_steps(&board);
+
+    for (given, solved) in board.cells.iter().zip(final_board.cells.iter()) {
+        if *given != 0 {
+            assert_eq!(given, solved, "a given clue was overwritten");
+        }
+    }
+}
+
+#[test]
+fn test_technique_level_ordering() {
+    assert!(TechniqueLevel::Basic < TechniqueLevel::Intermediate);
+    assert!(TechniqueLevel::Intermediate < TechniqueLevel::Advanced);
+    assert!(TechniqueLevel::Advanced < TechniqueLevel::Expert);
+}
+
+#[test]
+fn test_solve_with_steps_and_guesses_solves_a_stalled_puzzle() {
+    // A puzzle the pure logical techniques cannot finish; the guessing
+    // fallback must complete it and record at least one "Guess" step.
+    let puzzle_str =
+        "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..";
+    let solution_str =
+        "812753649943682175675491283154237896369845721287169534521974368438526917796318452";
+    let board = Board::from_str(puzzle_str).unwrap();
+
+    let (steps, solved) = logical_solver::solve_with_steps_and_guesses(&board);
+
+    assert!(!solved.cells.contains(&0));
+    assert_eq!(solved.to_string(), solution_str);
+    assert!(steps.iter().any(|s| s.technique == "Guess"));
+}
+
+#[test]
+fn test_wxyz_wing_eliminations_never_remove_the_solution_digit() {
+    // This stalled puzzle used to trigger a WXYZ-Wing on a 2-cell "group",
+    // wrongly eliminating the solution digit (8) from cell 25 and
+    // corrupting the board before backtracking ever ran. The rule only
+    // holds for groups of exactly four cells; every step's eliminations
+    // must agree with the known unique solution.
+    let puzzle_str =
+        "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..";
+    let solution_str =
+        "812753649943682175675491283154237896369845721287169534521974368438526917796318452";
+    let solution_digits: Vec<u8> = solution_str.bytes().map(|b| b - b'0').collect();
+
+    let board = Board::from_str(puzzle_str).unwrap();
+    let (steps, _) = logical_solver::solve_with_steps(&board);
+
+    for step in &steps {
+        for elim in &step.eliminations {
+            assert_ne!(
+                elim.value, solution_digits[elim.index],
+                "{} eliminated the solution digit {} from cell {}",
+                step.technique, elim.value, elim.index
+            );
+        }
+    }
+}
+
+#[test]
+fn test_count_solutions_respects_limit() {
+    let solution_str =
+        "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+    let unique_puzzle =
+        "...2..7...5..96832.8.7....641.....78.2..745..7.31854....2531..4.3164..5...9...61.";
+
+    let solved_board = Board::from_str(solution_str).unwrap();
+    assert_eq!(logical_solver::count_solutions(&solved_board, 2), 1);
+
+    let puzzle_board = Board::from_str(unique_puzzle).unwrap();
+    assert_eq!(logical_solver::count_solutions(&puzzle_board, 2), 1);
+
+    // An empty board has far more than two solutions; counting stops at the limit.
+    assert_eq!(logical_solver::count_solutions(&Board::empty(3), 2), 2);
+}
+
+#[test]
+fn test_solve_complete_matches_known_solution() {
+    let puzzle_str =
+        "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..";
+    let solution_str =
+        "812753649943682175675491283154237896369845721287169534521974368438526917796318452";
+    let board = Board::from_str(puzzle_str).unwrap();
+
+    let solved = logical_solver::solve_complete(&board).expect("puzzle has a solution");
+    assert_eq!(solved.to_string(), solution_str);
+}
+
 #[test]
 fn test_hybrid_solver_falls_back_to_backtracking() {
     // A very hard puzzle that the current logical solver cannot finish.
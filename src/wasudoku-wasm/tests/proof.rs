@@ -0,0 +1,77 @@
+/*
+* Copyright (C) 2025  Henrique Almeida
+* This file is part of WASudoku.
+
+* WASudoku is free software: you can redistribute it and/or modify
+* it under the terms of the GNU Affero General Public License as published
+* by the Free Software Foundation, either version 3 of the License, or
+* (at your option) any later version.
+
+* WASudoku is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+* GNU Affero General Public License for more details.
+
+* You should have received a copy of the GNU Affero General Public License
+* along with WASudoku.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use wasudoku_wasm::board::Board;
+use wasudoku_wasm::proof::{commit, open_unit, verify_unit};
+
+const SOLUTION: &str =
+    "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+
+#[test]
+fn test_verify_unit_accepts_honest_opening() {
+    let solution = Board::from_str(SOLUTION).unwrap();
+    // Same puzzle, with only the first row's first cell left as a clue.
+    let puzzle_str =
+        "5................................................................................";
+    let puzzle = Board::from_str(puzzle_str).unwrap();
+    let commitment = commit(&solution);
+
+    // Unit 0 is the first row; it should open and verify cleanly.
+    let opening = open_unit(&commitment, 0);
+    assert!(verify_unit(&commitment, &puzzle, &opening));
+}
+
+#[test]
+fn test_verify_unit_rejects_tampered_value() {
+    let solution = Board::from_str(SOLUTION).unwrap();
+    let puzzle = Board::empty(3);
+    let commitment = commit(&solution);
+
+    let mut opening = open_unit(&commitment, 0);
+    opening.values[0] = opening.values[0] % 9 + 1;
+
+    assert!(!verify_unit(&commitment, &puzzle, &opening));
+}
+
+#[test]
+fn test_verify_unit_rejects_mismatched_clue() {
+    let solution = Board::from_str(SOLUTION).unwrap();
+    // Claims the first cell is a 9, but the committed solution has a 5 there.
+    let puzzle_str =
+        "9................................................................................";
+    let puzzle = Board::from_str(puzzle_str).unwrap();
+    let commitment = commit(&solution);
+
+    let opening = open_unit(&commitment, 0);
+    assert!(!verify_unit(&commitment, &puzzle, &opening));
+}
+
+#[test]
+fn test_every_unit_verifies_for_a_full_solution() {
+    let solution = Board::from_str(SOLUTION).unwrap();
+    let puzzle = Board::empty(3);
+    let commitment = commit(&solution);
+
+    for unit_id in 0..27 {
+        let opening = open_unit(&commitment, unit_id);
+        assert!(
+            verify_unit(&commitment, &puzzle, &opening),
+            "unit {unit_id} failed to verify"
+        );
+    }
+}
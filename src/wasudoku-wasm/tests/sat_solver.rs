@@ -0,0 +1,63 @@
+/*
+* Copyright (C) 2025  Henrique Almeida
+* This file is part of WASudoku.
+
+* WASudoku is free software: you can redistribute it and/or modify
+* it under the terms of the GNU Affero General Public License as published
+* by the Free Software Foundation, either version 3 of the License, or
+* (at your option) any later version.
+
+* WASudoku is distributed in the hope that it will be useful,
+* but WITHOUT ANY WARRANTY; without even the implied warranty of
+* MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+* GNU Affero General Public License for more details.
+
+* You should have received a copy of the GNU Affero General Public License
+* along with WASudoku.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use wasudoku_wasm::board::Board;
+use wasudoku_wasm::sat_solver::{cdcl_count_solutions, cdcl_solve};
+
+const SOLUTION_STR: &str =
+    "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+
+/// Blank out the given cell indices in [`SOLUTION_STR`] and parse the result.
+fn board_with_blanks(indices: &[usize]) -> Board {
+    let mut cells: Vec<char> = SOLUTION_STR.chars().collect();
+    for &i in indices {
+        cells[i] = '.';
+    }
+    let puzzle_str: String = cells.into_iter().collect();
+    Board::from_str(&puzzle_str).unwrap()
+}
+
+#[test]
+fn test_cdcl_solve_one_missing_cell() {
+    let board = board_with_blanks(&[0]);
+
+    let solved = cdcl_solve(&board).expect("expected a model");
+    assert_eq!(solved.to_string(), SOLUTION_STR);
+}
+
+#[test]
+fn test_cdcl_solve_unsolvable_returns_none() {
+    let puzzle_str =
+        "...................................123456789.....................................";
+    let board = Board::from_str(puzzle_str).unwrap();
+    assert!(cdcl_solve(&board).is_none());
+}
+
+#[test]
+fn test_cdcl_count_solutions_unique() {
+    let board = board_with_blanks(&[0]);
+    assert_eq!(cdcl_count_solutions(&board, 2), 1);
+}
+
+#[test]
+fn test_cdcl_count_solutions_respects_limit() {
+    // Blanking the rectangle R3C5=1, R3C8=3, R4C5=3, R4C8=1 (two rows of one
+    // band, two boxes) leaves two completions: the 1s and 3s can swap.
+    let board = board_with_blanks(&[32, 35, 41, 44]);
+    assert_eq!(cdcl_count_solutions(&board, 1), 1);
+}
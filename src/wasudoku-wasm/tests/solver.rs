@@ -17,7 +17,7 @@
 */
 
 use wasudoku_wasm::board::Board;
-use wasudoku_wasm::solver::{count_solutions, solve, solve_randomized};
+use wasudoku_wasm::solver::{count_solutions, solve, solve_checked, solve_randomized, SolveError};
 
 #[test]
 fn test_solve_easy_puzzle() {
@@ -119,7 +119,7 @@ fn test_board_from_str_conflict_in_box() {
 
 #[test]
 fn test_solve_randomized_solves_empty_board() {
-    let mut board = Board { cells: [0; 81] };
+    let mut board = Board::empty(3);
     let numbers: [u8; 9] = [1, 2, 3, 4, 5, 6, 7, 8, 9];
     let solved = solve_randomized(&mut board, &numbers);
     assert!(solved);
@@ -150,6 +150,42 @@ fn test_count_solutions() {
     assert_eq!(count_solutions(&board), 0);
 }
 
+#[test]
+fn test_solve_checked_returns_the_unique_solution() {
+    let puzzle_str =
+        "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..";
+    let solution_str =
+        "812753649943682175675491283154237896369845721287169534521974368438526917796318452";
+    let board: Board = puzzle_str.parse().unwrap();
+
+    let solved = solve_checked(&board).expect("expected a unique solution");
+    assert_eq!(solved.to_string(), solution_str);
+}
+
+#[test]
+fn test_solve_checked_reports_unsolvable() {
+    let puzzle_str =
+        "...................................123456789.....................................";
+    let board: Board = puzzle_str.parse().unwrap();
+
+    match solve_checked(&board) {
+        Err(e) => assert_eq!(e, SolveError::Unsolvable),
+        Ok(_) => panic!("expected SolveError::Unsolvable"),
+    }
+}
+
+#[test]
+fn test_solve_checked_reports_multiple_solutions() {
+    let empty_str =
+        ".................................................................................";
+    let board: Board = empty_str.parse().unwrap();
+
+    match solve_checked(&board) {
+        Err(e) => assert_eq!(e, SolveError::MultipleSolutions),
+        Ok(_) => panic!("expected SolveError::MultipleSolutions"),
+    }
+}
+
 #[test]
 #[should_panic(expected = "Induced panic for testing")]
 #[cfg(feature = "test-panic")]
@@ -29,7 +29,7 @@ fn test_solve_sudoku_valid_puzzle() {
         "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
     let solution_str =
         "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
-    let result = solve_sudoku(puzzle_str).unwrap();
+    let result = solve_sudoku(puzzle_str, "backtracking").unwrap();
     let solve_result: wasudoku_wasm::types::SolveResult =
         serde_wasm_bindgen::from_value(result).unwrap();
 
@@ -43,7 +43,7 @@ fn test_solve_sudoku_no_solution() {
     // An unsolvable puzzle with conflicting givens that the solver can't satisfy.
     let puzzle_str =
         "1.2.3.4.5.6.7.8.9..............................................................";
-    let result = solve_sudoku(puzzle_str);
+    let result = solve_sudoku(puzzle_str, "backtracking");
     assert!(
         result.is_err(),
         "Expected an error for an unsolvable puzzle"
@@ -57,7 +57,7 @@ fn test_solve_sudoku_no_solution() {
 #[wasm_bindgen_test]
 fn test_solve_sudoku_invalid_board_string_length() {
     let puzzle_str = "123";
-    let result = solve_sudoku(puzzle_str);
+    let result = solve_sudoku(puzzle_str, "backtracking");
     assert!(
         result.is_err(),
         "Expected an error for invalid string length"
@@ -72,7 +72,7 @@ fn test_solve_sudoku_invalid_board_string_length() {
 fn test_solve_sudoku_invalid_board_string_char() {
     let puzzle_str =
         "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..7a";
-    let result = solve_sudoku(puzzle_str);
+    let result = solve_sudoku(puzzle_str, "backtracking");
     assert!(result.is_err(), "Expected an error for invalid character");
     assert_eq!(
         result.err().unwrap().as_string().unwrap(),
@@ -85,7 +85,7 @@ fn test_solve_sudoku_initial_conflict() {
     // Two '5's in the first row
     let puzzle_str =
         "53..7.5..6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
-    let result = solve_sudoku(puzzle_str);
+    let result = solve_sudoku(puzzle_str, "backtracking");
     assert!(result.is_err(), "Expected an error for initial conflict");
     assert_eq!(
         result.err().unwrap().as_string().unwrap(),
@@ -99,7 +99,7 @@ fn test_solve_sudoku_initial_conflict() {
 fn test_solve_sudoku_panic_handling() {
     let puzzle_str =
         "123..............................................................................";
-    let result = solve_sudoku(puzzle_str);
+    let result = solve_sudoku(puzzle_str, "backtracking");
     assert!(result.is_err(), "Expected an error from a panic");
     assert_eq!(
         result.err().unwrap().as_string().unwrap(),